@@ -0,0 +1,169 @@
+//! Arbitrary output variables: extra images with data
+//! about the first hit of each pixel, used for compositing
+//! and denoising.
+
+use std::collections::HashMap;
+
+use crate::{
+    color::Color,
+    ppm::PPMImage,
+    renderables::HitRecord,
+    vec3::{Point3, Vec3},
+};
+
+/// First hit data of a single pixel.
+#[derive(Debug, Clone)]
+pub(crate) struct AovSample {
+    depth: f32,
+    normal: Vec3,
+    albedo: Vec3,
+    position: Point3,
+    object: usize,
+    material: usize,
+}
+
+impl AovSample {
+    /// `view` is the normalized direction the camera looks at.
+    pub(crate) fn new(hit: &HitRecord, origin: Point3, view: Vec3) -> Self {
+        Self {
+            depth: (hit.point - origin).dot(view),
+            normal: hit.normal,
+            albedo: hit.material_ref.albedo(hit),
+            position: hit.point,
+            object: hit.object_id,
+            material: std::sync::Arc::as_ptr(&hit.material_ref)
+                .cast::<()>()
+                .addr(),
+        }
+    }
+}
+
+/// Buffers with the first hit of every pixel.
+///
+/// Pixels where nothing was hit have infinite depth,
+/// zero vectors and zero IDs.
+#[derive(Debug, Clone, Default)]
+pub struct Aovs {
+    pub width: usize,
+    pub height: usize,
+    /// Distance from the camera along its view direction.
+    pub depth: Vec<f32>,
+    /// World space normal, facing the camera.
+    pub normal: Vec<Vec3>,
+    pub albedo: Vec<Vec3>,
+    /// World space position.
+    pub position: Vec<Point3>,
+    /// Objects are numbered from 1 in order of
+    /// their first appearance, row by row.
+    pub object_id: Vec<u32>,
+    /// Numbered the same way as objects.
+    pub material_id: Vec<u32>,
+}
+
+impl Aovs {
+    pub(crate) fn from_samples(
+        width: usize,
+        height: usize,
+        samples: impl IntoIterator<Item = Option<AovSample>>,
+    ) -> Self {
+        let mut aovs = Self {
+            width,
+            height,
+            ..Self::default()
+        };
+        let mut objects = IdMap::default();
+        let mut materials = IdMap::default();
+        for sample in samples {
+            if let Some(sample) = sample {
+                aovs.depth.push(sample.depth);
+                aovs.normal.push(sample.normal);
+                aovs.albedo.push(sample.albedo);
+                aovs.position.push(sample.position);
+                aovs.object_id.push(objects.get(sample.object));
+                aovs.material_id.push(materials.get(sample.material));
+            } else {
+                aovs.depth.push(f32::INFINITY);
+                aovs.normal.push(Vec3::ZERO);
+                aovs.albedo.push(Vec3::ZERO);
+                aovs.position.push(Point3::ZERO);
+                aovs.object_id.push(0);
+                aovs.material_id.push(0);
+            }
+        }
+        aovs
+    }
+
+    /// Depth scaled so `max_depth` and farther is white.
+    #[must_use]
+    pub fn depth_image(&self, max_depth: f32) -> PPMImage {
+        self.image(self.depth.iter().map(|&depth| {
+            if depth.is_finite() {
+                Vec3::splat(depth / max_depth)
+            } else {
+                Vec3::ZERO
+            }
+        }))
+    }
+
+    /// Normals mapped from `-1..1` to `0..1`.
+    #[must_use]
+    pub fn normal_image(&self) -> PPMImage {
+        self.image(self.normal.iter().map(|&normal| {
+            if normal == Vec3::ZERO {
+                normal
+            } else {
+                normal * 0.5 + 0.5
+            }
+        }))
+    }
+
+    /// Albedo as is, without gamma, same as other AOVs.
+    #[must_use]
+    pub fn albedo_image(&self) -> PPMImage {
+        self.image(self.albedo.iter().copied())
+    }
+
+    /// Each ID gets its own random looking color.
+    #[must_use]
+    pub fn id_image(&self, ids: &[u32]) -> PPMImage {
+        self.image(ids.iter().map(|&id| id_color(id)))
+    }
+
+    /// Image of linear values, without gamma.
+    fn image(&self, values: impl Iterator<Item = Vec3>) -> PPMImage {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let to_byte = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+        let mut image = PPMImage::new(self.width, self.height);
+        image.data = values
+            .map(|value| Color::new(to_byte(value.x), to_byte(value.y), to_byte(value.z)))
+            .collect();
+        image
+    }
+}
+
+/// Gives small sequential IDs to arbitrary keys.
+#[derive(Default)]
+struct IdMap {
+    ids: HashMap<usize, u32>,
+}
+
+impl IdMap {
+    /// Zero key means "unknown" and always maps to zero.
+    fn get(&mut self, key: usize) -> u32 {
+        if key == 0 {
+            return 0;
+        }
+        let next = u32::try_from(self.ids.len() + 1).unwrap_or(u32::MAX);
+        *self.ids.entry(key).or_insert(next)
+    }
+}
+
+fn id_color(id: u32) -> Vec3 {
+    if id == 0 {
+        return Vec3::ZERO;
+    }
+    // Cheap integer hash, so neighbour IDs get different colors.
+    let hash = id.wrapping_mul(0x9E37_79B9).rotate_left(13).wrapping_mul(0x85EB_CA6B);
+    let channel = |shift: u32| f32::from(((hash >> shift) & 0xFF) as u8) / 255.;
+    Vec3::new(channel(0), channel(8), channel(16))
+}
@@ -1,15 +1,60 @@
-use rand::Rng;
+use std::{ops::Range, time::Duration};
+
+use glam::Vec2;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 use crate::{
-    color::Color,
-    interval::Interval,
+    aov::{AovSample, Aovs},
+    denoise::Denoiser,
+    filter::{Filter, SplatBuffer},
+    framebuffer::Framebuffer,
+    integrators::{Integrator, PathTracer, first_hit},
     ppm::PPMImage,
+    progressive::ProgressiveRender,
     ray::Ray,
-    renderables::{RayData, Renderable},
+    renderables::Renderable,
+    samplers::{Sampler, SamplerKind},
     vec3::{Point3, Vec3},
 };
 
+/// Spends samples where the image is noisy.
+///
+/// Each pixel gets at least `min_samples`, then keeps sampling
+/// until the estimated error of its brightness drops below
+/// `threshold` (relative to the brightness), or `max_samples` is reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveSampling {
+    pub min_samples: usize,
+    pub max_samples: usize,
+    pub threshold: f32,
+}
+
+impl AdaptiveSampling {
+    #[must_use]
+    pub fn new(min_samples: usize, max_samples: usize, threshold: f32) -> Self {
+        // Variance needs at least two samples.
+        let min_samples = min_samples.max(2);
+        Self {
+            min_samples,
+            max_samples: max_samples.max(min_samples),
+            threshold,
+        }
+    }
+}
+
+impl Default for AdaptiveSampling {
+    fn default() -> Self {
+        Self::new(8, 256, 0.02)
+    }
+}
+
+/// Brightness darker than this is treated as equal
+/// when comparing the error, so black pixels can converge.
+const MIN_ADAPTIVE_BRIGHTNESS: f32 = 0.05;
+
+/// Rows rendered together when splatting through a filter.
+const FILTER_BAND_HEIGHT: usize = 16;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Camera {
     pub origin: Point3,
@@ -19,6 +64,13 @@ pub struct Camera {
     pub max_depth: usize,
     pub fov: usize,
     anti_aliasing_scale: f32,
+    denoiser: Option<Denoiser>,
+    adaptive_sampling: Option<AdaptiveSampling>,
+    time_budget: Option<Duration>,
+    sampler: SamplerKind,
+    filter: Option<Filter>,
+    russian_roulette_depth: Option<usize>,
+    radiance_clamp: Option<f32>,
 
     focal_length: f32,
     output_height: usize,
@@ -27,26 +79,17 @@ pub struct Camera {
     viewport_delta_w: Vec3,
 }
 
-fn get_color_vec(ray: Ray, depth: usize, scene: &impl Renderable) -> Vec3 {
-    if depth == 0 {
-        return Vec3::ZERO;
-    }
-
-    let rd = RayData {
-        ray,
-        interval: Interval::new(0.001, f32::INFINITY),
-    };
+/// Scene together with the integrator that renders it.
+#[derive(Clone, Copy)]
+pub(crate) struct Tracer<'a> {
+    pub scene: &'a (dyn Renderable + Sync),
+    pub integrator: &'a dyn Integrator,
+}
 
-    if let Some(hit) = scene.hit(&rd) {
-        if let Some(mat_record) = hit.material_ref.scatter(&rd.ray, &hit) {
-            return mat_record.attenuation * get_color_vec(mat_record.ray, depth - 1, scene);
-        }
-        return Vec3::ZERO;
+impl Tracer<'_> {
+    fn color(&self, ray: Ray, sampler: &mut dyn Sampler) -> Vec3 {
+        self.integrator.color(ray, self.scene, sampler)
     }
-
-    let direction = ray.direction.normalize();
-    let a = (direction.y + 1.) * 0.5;
-    Vec3::ONE * (1. - a) + a * Vec3::new(0.5, 0.7, 1.0)
 }
 
 impl Camera {
@@ -64,6 +107,13 @@ impl Camera {
             focal_length,
             anti_aliasing_samples: 1,
             anti_aliasing_scale: 1.,
+            denoiser: None,
+            adaptive_sampling: None,
+            time_budget: None,
+            sampler: SamplerKind::Independent,
+            filter: None,
+            russian_roulette_depth: None,
+            radiance_clamp: None,
             max_depth: 100,
             fov: 90,
             aspect_ratio,
@@ -124,49 +174,365 @@ impl Camera {
         self.with_focal_length(self.focal_length)
     }
 
+    /// Makes `get_img` clean up the noise of the render.
+    /// It costs an extra pass for AOVs.
+    #[must_use]
+    pub const fn with_denoiser(mut self, denoiser: Option<Denoiser>) -> Self {
+        self.denoiser = denoiser;
+        self
+    }
+
+    /// Replaces fixed number of anti-aliasing samples
+    /// with adaptive sampling.
+    #[must_use]
+    pub const fn with_adaptive_sampling(mut self, adaptive: Option<AdaptiveSampling>) -> Self {
+        self.adaptive_sampling = adaptive;
+        self
+    }
+
+    /// Makes `get_img` add samples until the time runs out,
+    /// instead of using fixed or adaptive sample count.
+    #[must_use]
+    pub const fn with_time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.time_budget = budget;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_sampler(mut self, sampler: SamplerKind) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// Splats samples into neighbour pixels with the filter.
+    /// Without it, samples are averaged within their own pixel.
+    #[must_use]
+    pub const fn with_filter(mut self, filter: Option<Filter>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Randomly stops paths with low throughput
+    /// after the given number of bounces.
+    #[must_use]
+    pub const fn with_russian_roulette(mut self, min_depth: Option<usize>) -> Self {
+        self.russian_roulette_depth = min_depth;
+        self
+    }
+
+    /// Limits brightness of a single sample, which removes
+    /// fireflies at the cost of some energy.
+    #[must_use]
+    pub const fn with_radiance_clamp(mut self, clamp: Option<f32>) -> Self {
+        self.radiance_clamp = clamp;
+        self
+    }
+
+    #[must_use]
+    pub const fn output_height(&self) -> usize {
+        self.output_height
+    }
+
+    #[must_use]
+    pub const fn denoiser(&self) -> Option<&Denoiser> {
+        self.denoiser.as_ref()
+    }
+
+    #[must_use]
+    pub const fn filter(&self) -> Option<Filter> {
+        self.filter
+    }
+
+    /// Path tracer with depth, Russian roulette
+    /// and clamp settings of the camera.
+    #[must_use]
+    pub const fn path_tracer(&self) -> PathTracer {
+        PathTracer::new(self.max_depth)
+            .with_russian_roulette(self.russian_roulette_depth)
+            .with_radiance_clamp(self.radiance_clamp)
+    }
+
     #[must_use]
     pub fn get_img(&self, scene: &(impl Renderable + Sync)) -> PPMImage {
-        let pixels = (0..self.output_height)
+        self.get_img_with(scene, &self.path_tracer())
+    }
+
+    /// Same as `get_img`, but rendered by the given integrator.
+    #[must_use]
+    pub fn get_img_with(
+        &self,
+        scene: &(impl Renderable + Sync),
+        integrator: &dyn Integrator,
+    ) -> PPMImage {
+        if let Some(budget) = self.time_budget {
+            let mut render = self.progressive(scene).with_integrator(integrator);
+            render.pass_for(budget);
+            return render.to_image();
+        }
+        let image = self.render_with(scene, integrator);
+        match &self.denoiser {
+            Some(denoiser) => denoiser.denoise(&image, &self.get_aovs(scene)),
+            None => image,
+        }
+        .to_image()
+    }
+
+    /// Renders linear HDR image, without denoising.
+    #[must_use]
+    pub fn render(&self, scene: &(impl Renderable + Sync)) -> Framebuffer {
+        self.render_with(scene, &self.path_tracer())
+    }
+
+    /// Same as `render`, but rendered by the given integrator.
+    #[must_use]
+    pub fn render_with(
+        &self,
+        scene: &(impl Renderable + Sync),
+        integrator: &dyn Integrator,
+    ) -> Framebuffer {
+        let tracer = Tracer { scene, integrator };
+        if let Some(filter) = self.filter {
+            return self.render_filtered(&tracer, filter);
+        }
+        let data = (0..self.output_height * self.output_width)
             .into_par_iter()
-            .map(|y| {
-                (0..self.output_width)
-                    .into_par_iter()
-                    .map(|x| {
-                        if self.anti_aliasing_samples == 0 {
-                            self.get_color_simple(x, y, scene)
-                        } else {
-                            self.get_color_antialiased(x, y, scene)
-                        }
-                    })
-                    .collect::<Vec<_>>()
+            .map(|index| {
+                let (x, y) = (index % self.output_width, index / self.output_width);
+                match &self.adaptive_sampling {
+                    Some(adaptive) => self.get_color_adaptive(x, y, adaptive, &tracer),
+                    None if self.anti_aliasing_samples == 0 => self.get_color_simple(x, y, &tracer),
+                    None => self.get_color_antialiased(x, y, &tracer),
+                }
+            })
+            .collect();
+        Framebuffer {
+            width: self.output_width,
+            height: self.output_height,
+            data,
+        }
+    }
+
+    /// Splats every sample into all pixels covered by the filter.
+    fn render_filtered(&self, tracer: &Tracer, filter: Filter) -> Framebuffer {
+        let height = self.output_height;
+        self.splat_rows(0..height, filter, |x, y, buffer| {
+            if let Some(adaptive) = &self.adaptive_sampling {
+                self.sample_adaptive(x, y, adaptive, tracer, |offset, color| {
+                    buffer.splat(x, y, offset, color);
+                });
+                return;
+            }
+            let sample_count = self.anti_aliasing_samples.max(1);
+            let mut sampler = self.sampler.create(x, y, sample_count);
+            for index in 0..sample_count {
+                let (offset, color) = self.sample_jittered(x, y, index, sampler.as_mut(), tracer);
+                buffer.splat(x, y, offset, color);
+            }
+        })
+        .resolve()
+    }
+
+    /// Calls `sample_pixel` for every pixel of the rows, to splat
+    /// its samples into a buffer which covers the rows.
+    ///
+    /// Rows are rendered in bands in parallel. Each band gets its own
+    /// buffer with extra rows for splats over its edges, and bands
+    /// are summed up at the end.
+    pub(crate) fn splat_rows(
+        &self,
+        rows: Range<usize>,
+        filter: Filter,
+        sample_pixel: impl Fn(usize, usize, &mut SplatBuffer) + Sync,
+    ) -> SplatBuffer {
+        let (width, height) = (self.output_width, self.output_height);
+        let bands = rows
+            .clone()
+            .step_by(FILTER_BAND_HEIGHT)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|start| {
+                let end = (start + FILTER_BAND_HEIGHT).min(rows.end);
+                let mut buffer = SplatBuffer::new(filter, width, height, start..end);
+                for y in start..end {
+                    for x in 0..width {
+                        sample_pixel(x, y, &mut buffer);
+                    }
+                }
+                buffer
             })
             .collect::<Vec<_>>();
-        pixels.into()
+
+        let mut buffer = SplatBuffer::new(filter, width, height, rows);
+        for band in bands {
+            buffer.add(&band);
+        }
+        buffer
+    }
+
+    /// Starts a render that adds one sample per pixel on each pass.
+    /// Anti-aliasing and adaptive sampling settings are ignored.
+    ///
+    /// It uses the path tracer of the camera, unless
+    /// another integrator is set on the render.
+    #[must_use]
+    pub fn progressive<'a, S: Renderable + Sync>(
+        &'a self,
+        scene: &'a S,
+    ) -> ProgressiveRender<'a, S> {
+        ProgressiveRender::new(self, scene)
+    }
+
+    /// Renders progressively until the time runs out.
+    ///
+    /// Returns the image and the number of samples that
+    /// every pixel has got. Some rows might have one more.
+    /// Denoising, if enabled, takes time on top of the budget.
+    #[must_use]
+    pub fn render_for(
+        &self,
+        scene: &(impl Renderable + Sync),
+        budget: Duration,
+    ) -> (PPMImage, usize) {
+        let mut render = self.progressive(scene);
+        render.pass_for(budget);
+        (render.to_image(), render.passes())
     }
 
-    fn get_color_simple(&self, x: usize, y: usize, scene: &impl Renderable) -> Color {
+    /// Renders the image together with first hit buffers.
+    #[must_use]
+    pub fn get_img_with_aovs(&self, scene: &(impl Renderable + Sync)) -> (PPMImage, Aovs) {
+        (self.get_img(scene), self.get_aovs(scene))
+    }
+
+    /// Renders only first hit buffers, through pixel centers.
+    ///
+    /// Surfaces are seen when they are more than half opaque.
+    #[must_use]
+    pub fn get_aovs(&self, scene: &(impl Renderable + Sync)) -> Aovs {
+        let view = Vec3::NEG_Z;
+        let samples = (0..self.output_height * self.output_width)
+            .into_par_iter()
+            .map(|index| {
+                let ray = self.pixel_ray(index % self.output_width, index / self.output_width);
+                first_hit(ray, scene, 0.5).map(|hit| AovSample::new(&hit, self.origin, view))
+            })
+            .collect::<Vec<_>>();
+        Aovs::from_samples(self.output_width, self.output_height, samples)
+    }
+
+    fn pixel_ray(&self, x: usize, y: usize) -> Ray {
         let pixel_center = self.viewport_start
             + (self.viewport_delta_w * x as f32)
             + (self.viewport_delta_h * y as f32);
-        let ray_direction = pixel_center - self.origin;
-        let ray = Ray::new(self.origin, ray_direction);
-        Color::from(get_color_vec(ray, self.max_depth, scene))
+        Ray::new(self.origin, pixel_center - self.origin)
     }
 
-    fn get_color_antialiased(&self, x: usize, y: usize, scene: &impl Renderable) -> Color {
-        let mut rng = rand::rng();
+    fn get_color_simple(&self, x: usize, y: usize, tracer: &Tracer) -> Vec3 {
+        let mut sampler = self.sampler.create(x, y, 1);
+        tracer.color(self.pixel_ray(x, y), sampler.as_mut())
+    }
+
+    fn get_color_antialiased(&self, x: usize, y: usize, tracer: &Tracer) -> Vec3 {
+        let mut sampler = self.sampler.create(x, y, self.anti_aliasing_samples);
         let mut color_vec = Vec3::ZERO;
-        for _ in 0..self.anti_aliasing_samples {
-            let offset_x: f32 = rng.random_range(-0.5..=0.5);
-            let offset_y: f32 = rng.random_range(-0.5..=0.5);
-            let pixel_center = self.viewport_start
-                + (self.viewport_delta_w * (x as f32 + offset_x))
-                + (self.viewport_delta_h * (y as f32 + offset_y));
-            let ray_direction = pixel_center - self.origin;
-            let ray = Ray::new(self.origin, ray_direction);
-            color_vec += get_color_vec(ray, self.max_depth, scene);
+        for index in 0..self.anti_aliasing_samples {
+            color_vec += self
+                .sample_jittered(x, y, index, sampler.as_mut(), tracer)
+                .1;
+        }
+
+        color_vec * self.anti_aliasing_scale
+    }
+
+    fn get_color_adaptive(
+        &self,
+        x: usize,
+        y: usize,
+        adaptive: &AdaptiveSampling,
+        tracer: &Tracer,
+    ) -> Vec3 {
+        self.sample_adaptive(x, y, adaptive, tracer, |_, _| {})
+    }
+
+    /// Samples the pixel until it converges. Each sample is passed
+    /// to `each` with its offset from the pixel center.
+    /// Returns the mean color.
+    fn sample_adaptive(
+        &self,
+        x: usize,
+        y: usize,
+        adaptive: &AdaptiveSampling,
+        tracer: &Tracer,
+        mut each: impl FnMut(Vec2, Vec3),
+    ) -> Vec3 {
+        let mut sampler = self.sampler.create(x, y, adaptive.max_samples);
+        let mut mean = Vec3::ZERO;
+        // Running variance of brightness by Welford's algorithm.
+        let mut mean_brightness = 0.;
+        let mut squares_sum = 0.;
+        for count in 1..=adaptive.max_samples {
+            let (offset, color) = self.sample_jittered(x, y, count - 1, sampler.as_mut(), tracer);
+            each(offset, color);
+            let n = count as f32;
+            mean += (color - mean) / n;
+            let brightness = color.dot(Vec3::splat(1. / 3.));
+            let delta = brightness - mean_brightness;
+            mean_brightness += delta / n;
+            squares_sum += delta * (brightness - mean_brightness);
+
+            if count >= adaptive.min_samples {
+                let error = (squares_sum / (n * (n - 1.))).sqrt();
+                if error <= adaptive.threshold * mean_brightness.max(MIN_ADAPTIVE_BRIGHTNESS) {
+                    break;
+                }
+            }
         }
+        mean
+    }
 
-        Color::from(color_vec * self.anti_aliasing_scale)
+    /// Sample of the pixel with given index, with its
+    /// offset from the pixel center.
+    ///
+    /// Sampler expects as many samples as set for anti-aliasing.
+    pub(crate) fn sample_pixel(
+        &self,
+        x: usize,
+        y: usize,
+        index: usize,
+        tracer: &Tracer,
+    ) -> (Vec2, Vec3) {
+        let mut sampler = self.sampler.create(x, y, self.anti_aliasing_samples);
+        self.sample_jittered(x, y, index, sampler.as_mut(), tracer)
+    }
+
+    /// Offset of a random point inside of the pixel and its color.
+    fn sample_jittered(
+        &self,
+        x: usize,
+        y: usize,
+        index: usize,
+        sampler: &mut dyn Sampler,
+        tracer: &Tracer,
+    ) -> (Vec2, Vec3) {
+        sampler.start_sample(index);
+        let offset = sampler.pixel() - 0.5;
+        (offset, self.get_color_at(x, y, offset, sampler, tracer))
+    }
+
+    /// Color at `offset` from the pixel center.
+    fn get_color_at(
+        &self,
+        x: usize,
+        y: usize,
+        offset: Vec2,
+        sampler: &mut dyn Sampler,
+        tracer: &Tracer,
+    ) -> Vec3 {
+        let pixel_center = self.viewport_start
+            + (self.viewport_delta_w * (x as f32 + offset.x))
+            + (self.viewport_delta_h * (y as f32 + offset.y));
+        let ray_direction = pixel_center - self.origin;
+        let ray = Ray::new(self.origin, ray_direction);
+        tracer.color(ray, sampler)
     }
 }
@@ -0,0 +1,184 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{aov::Aovs, framebuffer::Framebuffer, vec3::Vec3};
+
+/// Joint bilateral filter for noisy renders.
+///
+/// Neighbour pixels are averaged only when they look like
+/// the same surface: similar normal, albedo and depth.
+/// So edges and textures stay sharp, while the noise
+/// of lighting is blurred away.
+///
+/// Lighting is filtered without albedo, and albedo is
+/// multiplied back afterwards, so textures don't get blurry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Denoiser {
+    /// Half size of the filter window in pixels.
+    pub radius: usize,
+    pub sigma_spatial: f32,
+    /// Difference in lighting, relative to its brightness.
+    pub sigma_color: f32,
+    pub sigma_normal: f32,
+    pub sigma_albedo: f32,
+    /// Difference in depth, relative to the depth.
+    pub sigma_depth: f32,
+}
+
+impl Default for Denoiser {
+    fn default() -> Self {
+        Self {
+            radius: 6,
+            sigma_spatial: 3.,
+            sigma_color: 1.,
+            sigma_normal: 0.2,
+            sigma_albedo: 0.1,
+            sigma_depth: 0.05,
+        }
+    }
+}
+
+/// Lighting darker than this is treated as equal,
+/// so its noise doesn't split areas in shadows.
+const MIN_BRIGHTNESS: f32 = 0.05;
+
+/// Albedo is never darker than this when lighting is
+/// separated from it, to avoid division by zero.
+const MIN_ALBEDO: f32 = 1e-2;
+
+/// Sigmas are never smaller than this, as they are divisors.
+const MIN_SIGMA: f32 = 1e-3;
+
+/// Depth of hits right at the camera is treated as this,
+/// as depth difference is relative to it.
+const MIN_DEPTH: f32 = 1e-4;
+
+impl Denoiser {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub const fn with_radius(mut self, radius: usize) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_sigma_spatial(mut self, sigma: f32) -> Self {
+        self.sigma_spatial = sigma;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_sigma_color(mut self, sigma: f32) -> Self {
+        self.sigma_color = sigma;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_sigma_normal(mut self, sigma: f32) -> Self {
+        self.sigma_normal = sigma;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_sigma_albedo(mut self, sigma: f32) -> Self {
+        self.sigma_albedo = sigma;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_sigma_depth(mut self, sigma: f32) -> Self {
+        self.sigma_depth = sigma;
+        self
+    }
+
+    /// `aovs` should be rendered with the same camera as `image`.
+    ///
+    /// # Panics
+    ///
+    /// If sizes of the image and AOVs differ.
+    #[must_use]
+    pub fn denoise(&self, image: &Framebuffer, aovs: &Aovs) -> Framebuffer {
+        assert_eq!(
+            (image.width, image.height),
+            (aovs.width, aovs.height),
+            "AOVs should have the same size as the image"
+        );
+        let lighting: Vec<Vec3> = image
+            .data
+            .iter()
+            .zip(&aovs.albedo)
+            .map(|(&color, &albedo)| color / albedo.max(Vec3::splat(MIN_ALBEDO)))
+            .collect();
+
+        let data = (0..image.width * image.height)
+            .into_par_iter()
+            .map(|index| {
+                // Background has nothing to guide the filter, and it's not noisy anyway.
+                if !aovs.depth[index].is_finite() {
+                    return image.data[index];
+                }
+                let albedo = aovs.albedo[index].max(Vec3::splat(MIN_ALBEDO));
+                albedo * self.filter_pixel(index, image.width, image.height, &lighting, aovs)
+            })
+            .collect();
+
+        Framebuffer {
+            width: image.width,
+            height: image.height,
+            data,
+        }
+    }
+
+    fn filter_pixel(
+        &self,
+        index: usize,
+        width: usize,
+        height: usize,
+        lighting: &[Vec3],
+        aovs: &Aovs,
+    ) -> Vec3 {
+        let (x, y) = (index % width, index / width);
+        let center = lighting[index];
+        let center_depth = aovs.depth[index].max(MIN_DEPTH);
+        let center_brightness = brightness(center);
+
+        let mut sum = Vec3::ZERO;
+        let mut total_weight = 0.;
+        for ny in y.saturating_sub(self.radius)..(y + self.radius + 1).min(height) {
+            for nx in x.saturating_sub(self.radius)..(x + self.radius + 1).min(width) {
+                let other = ny * width + nx;
+                if !aovs.depth[other].is_finite() {
+                    continue;
+                }
+                let dx = nx as f32 - x as f32;
+                let dy = ny as f32 - y as f32;
+                let exponent = dx.mul_add(dx, dy * dy) / self.sigma_spatial.max(MIN_SIGMA).powi(2)
+                    // Difference is relative to the brighter pixel, so dark
+                    // and bright areas are filtered the same way, and noisy
+                    // dark pixels still get their neighbours.
+                    + ((lighting[other] - center)
+                        / center_brightness.max(brightness(lighting[other])))
+                    .length_squared()
+                        / self.sigma_color.max(MIN_SIGMA).powi(2)
+                    + (aovs.normal[other] - aovs.normal[index]).length_squared()
+                        / self.sigma_normal.max(MIN_SIGMA).powi(2)
+                    + (aovs.albedo[other] - aovs.albedo[index]).length_squared()
+                        / self.sigma_albedo.max(MIN_SIGMA).powi(2)
+                    + ((aovs.depth[other] - center_depth) / center_depth).powi(2)
+                        / self.sigma_depth.max(MIN_SIGMA).powi(2);
+                let weight = (-0.5 * exponent).exp();
+                sum += lighting[other] * weight;
+                total_weight += weight;
+            }
+        }
+        // Center pixel always has weight 1, so it's never zero.
+        sum / total_weight
+    }
+}
+
+fn brightness(color: Vec3) -> f32 {
+    color.dot(Vec3::splat(1. / 3.)).max(MIN_BRIGHTNESS)
+}
@@ -0,0 +1,217 @@
+use std::{f32::consts::PI, ops::Range};
+
+use glam::{Vec2, Vec3, Vec4};
+
+use crate::framebuffer::Framebuffer;
+
+/// Shape of a pixel reconstruction filter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterShape {
+    /// Equal weight everywhere within the radius.
+    Box,
+    /// Weight falls linearly to zero at the radius.
+    Tent,
+    /// Gaussian shifted down to reach zero at the radius.
+    Gaussian { sigma: f32 },
+    /// Mitchell-Netravali cubic. `b = c = 1/3` is the
+    /// recommended balance between blur and ringing.
+    Mitchell { b: f32, c: f32 },
+    /// Sinc windowed by a wider sinc. Sharp, but rings a bit.
+    Lanczos,
+}
+
+/// Decides how much a sample contributes to pixels around it.
+///
+/// Samples are splatted into every pixel whose center is within
+/// `radius` from the sample, weighted by the filter. Radius is
+/// in pixels, so a box with radius 0.5 covers exactly one pixel.
+/// Filters are separable: weight is a product of weights by X and Y.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Filter {
+    pub shape: FilterShape,
+    pub radius: f32,
+}
+
+impl Filter {
+    #[must_use]
+    pub const fn new(shape: FilterShape, radius: f32) -> Self {
+        Self { shape, radius }
+    }
+
+    #[must_use]
+    pub const fn box_filter(radius: f32) -> Self {
+        Self::new(FilterShape::Box, radius)
+    }
+
+    #[must_use]
+    pub const fn tent(radius: f32) -> Self {
+        Self::new(FilterShape::Tent, radius)
+    }
+
+    #[must_use]
+    pub const fn gaussian(radius: f32, sigma: f32) -> Self {
+        Self::new(FilterShape::Gaussian { sigma }, radius)
+    }
+
+    #[must_use]
+    pub const fn mitchell(radius: f32, b: f32, c: f32) -> Self {
+        Self::new(FilterShape::Mitchell { b, c }, radius)
+    }
+
+    #[must_use]
+    pub const fn lanczos(radius: f32) -> Self {
+        Self::new(FilterShape::Lanczos, radius)
+    }
+
+    /// Weight of a sample at `offset` from the pixel center.
+    #[must_use]
+    pub fn evaluate(&self, offset: Vec2) -> f32 {
+        self.evaluate_1d(offset.x) * self.evaluate_1d(offset.y)
+    }
+
+    fn evaluate_1d(&self, x: f32) -> f32 {
+        let x = x.abs();
+        if x > self.radius {
+            return 0.;
+        }
+        match self.shape {
+            FilterShape::Box => 1.,
+            FilterShape::Tent => self.radius - x,
+            FilterShape::Gaussian { sigma } => {
+                let gaussian = |x: f32| (-x * x / (2. * sigma * sigma)).exp();
+                (gaussian(x) - gaussian(self.radius)).max(0.)
+            }
+            // The cubic is defined on -2..2, so it's stretched to the radius.
+            FilterShape::Mitchell { b, c } => mitchell(2. * x / self.radius, b, c),
+            FilterShape::Lanczos => sinc(x) * sinc(x / self.radius),
+        }
+    }
+}
+
+impl Default for Filter {
+    /// Box over a single pixel, the same as without filtering.
+    fn default() -> Self {
+        Self::box_filter(0.5)
+    }
+}
+
+fn mitchell(x: f32, b: f32, c: f32) -> f32 {
+    // Coefficients of the cubic are linear combinations of 1, B and C.
+    let bc = Vec3::new(1., b, c);
+    let coefficients = if x < 1. {
+        [
+            Vec3::new(12., -9., -6.),
+            Vec3::new(-18., 12., 6.),
+            Vec3::ZERO,
+            Vec3::new(6., -2., 0.),
+        ]
+    } else if x < 2. {
+        [
+            Vec3::new(0., -1., -6.),
+            Vec3::new(0., 6., 30.),
+            Vec3::new(0., -12., -48.),
+            Vec3::new(0., 8., 24.),
+        ]
+    } else {
+        return 0.;
+    };
+    let powers = Vec4::new(x * x * x, x * x, x, 1.);
+    powers.dot(Vec4::from_array(
+        coefficients.map(|coefficient| coefficient.dot(bc)),
+    )) / 6.
+}
+
+fn sinc(x: f32) -> f32 {
+    if x < 1e-5 {
+        1.
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Weighted sums of samples for a range of rows.
+#[derive(Debug, Clone)]
+pub(crate) struct SplatBuffer {
+    filter: Filter,
+    /// How far splats reach, in whole pixels.
+    reach: usize,
+    width: usize,
+    height: usize,
+    /// First row of the buffer in the image.
+    top: usize,
+    sums: Vec<Vec3>,
+    weights: Vec<f32>,
+}
+
+impl SplatBuffer {
+    /// Covers `rows` and all rows their samples reach.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn new(filter: Filter, width: usize, height: usize, rows: Range<usize>) -> Self {
+        let reach = filter.radius.max(0.).ceil() as usize;
+        let top = rows.start.saturating_sub(reach);
+        let bottom = (rows.end + reach).min(height);
+        Self {
+            filter,
+            reach,
+            width,
+            height,
+            top,
+            sums: vec![Vec3::ZERO; (bottom - top) * width],
+            weights: vec![0.; (bottom - top) * width],
+        }
+    }
+
+    /// Adds a sample at `offset` from the center of pixel `x`, `y`.
+    pub(crate) fn splat(&mut self, x: usize, y: usize, offset: Vec2, color: Vec3) {
+        let rows = y.saturating_sub(self.reach)..(y + self.reach + 1).min(self.height);
+        let columns = x.saturating_sub(self.reach)..(x + self.reach + 1).min(self.width);
+        for ny in rows {
+            for nx in columns.clone() {
+                // From the sample to the center of the other pixel.
+                let to_center = Vec2::new(nx as f32 - x as f32, ny as f32 - y as f32) - offset;
+                let weight = self.filter.evaluate(to_center);
+                if weight != 0. {
+                    let index = (ny - self.top) * self.width + nx;
+                    self.sums[index] += color * weight;
+                    self.weights[index] += weight;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn add(&mut self, other: &Self) {
+        let offset = (other.top - self.top) * self.width;
+        for (index, (sum, weight)) in other.sums.iter().zip(&other.weights).enumerate() {
+            self.sums[offset + index] += *sum;
+            self.weights[offset + index] += weight;
+        }
+    }
+
+    /// Adds a sample that only counts for its own pixel.
+    pub(crate) fn accumulate(&mut self, x: usize, y: usize, color: Vec3) {
+        let index = (y - self.top) * self.width + x;
+        self.sums[index] += color;
+        self.weights[index] += 1.;
+    }
+
+    /// Normalizes sums by weights.
+    pub(crate) fn resolve(&self) -> Framebuffer {
+        Framebuffer {
+            width: self.width,
+            height: self.sums.len() / self.width.max(1),
+            data: self
+                .sums
+                .iter()
+                .zip(&self.weights)
+                .map(|(&sum, &weight)| {
+                    // Filters with negative lobes may cancel out.
+                    if weight.abs() > f32::EPSILON {
+                        sum / weight
+                    } else {
+                        Vec3::ZERO
+                    }
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    fs::OpenOptions,
+    io::{BufWriter, Write},
+};
+
+use crate::{color::Color, ppm::PPMImage, vec3::Vec3};
+
+/// Linear HDR image, as it comes out of the renderer.
+///
+/// Values aren't limited to `0..1`. They are mapped
+/// to displayable colors only on conversion to `PPMImage`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Framebuffer {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<Vec3>,
+}
+
+impl Framebuffer {
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![Vec3::ZERO; width * height],
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Vec3 {
+        self.data[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: Vec3) {
+        self.data[y * self.width + x] = value;
+    }
+
+    /// Applies gamma and clamps values to displayable range.
+    #[must_use]
+    pub fn to_image(&self) -> PPMImage {
+        PPMImage {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(|&value| Color::from(value)).collect(),
+        }
+    }
+
+    /// Saves linear values to a PFM file, keeping the full range.
+    pub fn save_pfm(&self, filename: &str) -> anyhow::Result<()> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(filename)?;
+        let mut writer = BufWriter::new(file);
+        // Negative scale means little endian.
+        write!(&mut writer, "PF\n{} {}\n-1.0\n", self.width, self.height)?;
+        // PFM stores rows from bottom to top.
+        for row in self.data.chunks(self.width.max(1)).rev() {
+            for value in row {
+                for channel in value.to_array() {
+                    writer.write_all(&channel.to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&Framebuffer> for PPMImage {
+    fn from(value: &Framebuffer) -> Self {
+        value.to_image()
+    }
+}
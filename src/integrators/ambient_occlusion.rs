@@ -0,0 +1,63 @@
+use crate::{
+    integrators::{Integrator, utils::first_hit},
+    interval::Interval,
+    materials::sphere_direction,
+    ray::Ray,
+    renderables::{RayData, Renderable},
+    samplers::Sampler,
+    vec3::{Vec3, Vec3Ext},
+};
+
+/// Shows how open each point is to its surroundings.
+///
+/// Random rays are cast around the normal, and the color
+/// is the share of light they let through within `distance`,
+/// so transparent surfaces and volumes occlude partially.
+/// Rays that hit nothing at all are white.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmbientOcclusion {
+    pub distance: f32,
+    /// Rays per camera sample.
+    pub samples: usize,
+}
+
+impl AmbientOcclusion {
+    #[must_use]
+    pub const fn new(distance: f32) -> Self {
+        Self {
+            distance,
+            samples: 1,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples;
+        self
+    }
+}
+
+impl Integrator for AmbientOcclusion {
+    fn color(&self, ray: Ray, scene: &dyn Renderable, sampler: &mut dyn Sampler) -> Vec3 {
+        let Some(hit) = first_hit(ray, scene, sampler.bounce(0).opacity) else {
+            return Vec3::ONE;
+        };
+        let sample_count = self.samples.max(1);
+        let open: f32 = (0..sample_count)
+            .map(|index| {
+                // First bounce was used by the camera ray.
+                let sample = sampler.bounce(index + 1);
+                // Unit vector added to the normal gives cosine-weighted direction.
+                let mut direction = hit.normal + sphere_direction(sample.direction);
+                if direction.near_zero() {
+                    direction = hit.normal;
+                }
+                scene.transmittance(&RayData {
+                    ray: Ray::new(hit.point, direction),
+                    interval: Interval::new(0.001, self.distance),
+                })
+            })
+            .sum();
+        Vec3::splat(open / sample_count as f32)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    integrators::{Integrator, utils::first_hit},
+    interval::Interval,
+    ray::Ray,
+    renderables::{RayData, Renderable},
+    samplers::Sampler,
+    vec3::Vec3,
+};
+
+/// Hits counted along a ray are capped by this, in case
+/// a surface keeps getting hit at the same distance.
+const MAX_HIT_COUNT: usize = 1024;
+
+/// Shows data of the first hit instead of lighting.
+/// Misses are black.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebugView {
+    /// Normals mapped from `-1..1` to `0..1`.
+    Normals,
+    /// Distance along the ray, white at `max_distance`.
+    Depth { max_distance: f32 },
+    /// U in red and V in green, repeated every unit.
+    Uvs,
+    /// Number of surfaces the ray passes through,
+    /// white at `max_hits`. Shows how heavy the scene is.
+    HitCount { max_hits: usize },
+}
+
+impl Integrator for DebugView {
+    fn color(&self, ray: Ray, scene: &dyn Renderable, sampler: &mut dyn Sampler) -> Vec3 {
+        // Hit is only needed by views of the first surface.
+        let mut first = || first_hit(ray, scene, sampler.bounce(0).opacity);
+        match *self {
+            Self::Normals => first().map_or(Vec3::ZERO, |hit| hit.normal * 0.5 + 0.5),
+            Self::Depth { max_distance } => first().map_or(Vec3::ZERO, |hit| {
+                Vec3::splat(hit.distance * ray.direction.length() / max_distance)
+            }),
+            Self::Uvs => first().map_or(Vec3::ZERO, |hit| hit.uv.fract_gl().extend(0.)),
+            Self::HitCount { max_hits } => {
+                Vec3::splat(hit_count(ray, scene) as f32 / max_hits.max(1) as f32)
+            }
+        }
+    }
+}
+
+fn hit_count(ray: Ray, scene: &dyn Renderable) -> usize {
+    let mut rd = RayData {
+        ray,
+        interval: Interval::new(0.001, f32::INFINITY),
+    };
+    let mut count = 0;
+    while count < MAX_HIT_COUNT
+        && let Some(hit) = scene.hit(&rd)
+    {
+        count += 1;
+        rd.interval.min = hit.distance + 0.001;
+    }
+    count
+}
@@ -0,0 +1,14 @@
+mod ambient_occlusion;
+mod debug;
+mod path;
+mod traits;
+mod utils;
+mod whitted;
+
+pub use ambient_occlusion::AmbientOcclusion;
+pub use debug::DebugView;
+pub use path::PathTracer;
+pub use traits::Integrator;
+pub(crate) use utils::first_hit;
+pub use utils::sky_color;
+pub use whitted::Whitted;
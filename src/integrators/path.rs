@@ -0,0 +1,92 @@
+use crate::{
+    integrators::{
+        Integrator,
+        utils::{first_hit, sky_color},
+    },
+    ray::Ray,
+    renderables::Renderable,
+    samplers::Sampler,
+    vec3::Vec3,
+};
+
+/// Unbiased path tracer. Follows a single scattered ray
+/// on every bounce and sums up emission along the path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathTracer {
+    pub max_depth: usize,
+    pub russian_roulette_depth: Option<usize>,
+    pub radiance_clamp: Option<f32>,
+}
+
+impl PathTracer {
+    #[must_use]
+    pub const fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            russian_roulette_depth: None,
+            radiance_clamp: None,
+        }
+    }
+
+    /// Randomly stops paths with low throughput
+    /// after the given number of bounces.
+    #[must_use]
+    pub const fn with_russian_roulette(mut self, min_depth: Option<usize>) -> Self {
+        self.russian_roulette_depth = min_depth;
+        self
+    }
+
+    /// Limits brightness of a single sample, which removes
+    /// fireflies at the cost of some energy.
+    #[must_use]
+    pub const fn with_radiance_clamp(mut self, clamp: Option<f32>) -> Self {
+        self.radiance_clamp = clamp;
+        self
+    }
+}
+
+impl Integrator for PathTracer {
+    /// Path is followed bounce by bounce, keeping the product
+    /// of attenuations seen so far as throughput.
+    fn color(&self, ray: Ray, scene: &dyn Renderable, sampler: &mut dyn Sampler) -> Vec3 {
+        let mut ray = ray;
+        let mut color = Vec3::ZERO;
+        let mut throughput = Vec3::ONE;
+        for bounce in 0..self.max_depth {
+            let sample = sampler.bounce(bounce);
+            let Some(hit) = first_hit(ray, scene, sample.opacity) else {
+                color += throughput * sky_color(&ray);
+                break;
+            };
+            color += throughput * hit.material_ref.emitted(&hit);
+
+            let Some(mut mat_record) = hit.material_ref.scatter_sampled(&ray, &hit, &sample) else {
+                break;
+            };
+            // Once a ray was split by wavelength,
+            // it carries this wavelength till the end of its path.
+            mat_record.ray.wavelength = mat_record.ray.wavelength.or(ray.wavelength);
+            throughput *= mat_record.attenuation;
+
+            if self
+                .russian_roulette_depth
+                .is_some_and(|depth| bounce + 1 >= depth)
+            {
+                // Dim paths are likely to stop. Survivors are
+                // brightened, so on average the result is the same.
+                let survival = throughput.max_element().min(1.);
+                if sample.termination >= survival {
+                    break;
+                }
+                throughput /= survival;
+            }
+            ray = mat_record.ray;
+        }
+
+        match self.radiance_clamp {
+            // Scaling keeps the hue, unlike clamping each channel.
+            Some(clamp) if color.max_element() > clamp => color * (clamp / color.max_element()),
+            _ => color,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt::Debug;
+
+use crate::{ray::Ray, renderables::Renderable, samplers::Sampler, vec3::Vec3};
+
+/// Algorithm that finds the color seen along a camera ray.
+///
+/// Camera only generates rays and averages the results,
+/// so the same scene can be rendered in different ways.
+pub trait Integrator: Debug + Send + Sync {
+    fn color(&self, ray: Ray, scene: &dyn Renderable, sampler: &mut dyn Sampler) -> Vec3;
+}
+
+impl<T: Integrator + ?Sized> Integrator for &T {
+    fn color(&self, ray: Ray, scene: &dyn Renderable, sampler: &mut dyn Sampler) -> Vec3 {
+        (**self).color(ray, scene, sampler)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{
+    interval::Interval,
+    ray::Ray,
+    renderables::{HitRecord, RayData, Renderable},
+    vec3::Vec3,
+};
+
+/// Closest hit, skipping transparent parts of surfaces.
+///
+/// `choice` in `0..1` decides which surfaces are skipped,
+/// the ray goes through a surface if it's not below its opacity.
+pub fn first_hit(ray: Ray, scene: &dyn Renderable, choice: f32) -> Option<HitRecord> {
+    let mut choice = choice;
+    let mut rd = RayData {
+        ray,
+        interval: Interval::new(0.001, f32::INFINITY),
+    };
+
+    while let Some(hit) = scene.hit(&rd) {
+        let opacity = hit.material_ref.opacity(&hit);
+        if choice >= opacity {
+            // Transparent part of the surface, look for the next one.
+            // Choice is rescaled to `0..1`, so it can decide again.
+            choice = (choice - opacity) / (1. - opacity);
+            rd.interval.min = hit.distance + 0.001;
+            continue;
+        }
+        return Some(hit);
+    }
+    None
+}
+
+/// Background for rays which hit nothing.
+#[must_use]
+pub fn sky_color(ray: &Ray) -> Vec3 {
+    let direction = ray.direction.normalize();
+    let a = (direction.y + 1.) * 0.5;
+    Vec3::ONE * (1. - a) + a * Vec3::new(0.5, 0.7, 1.0)
+}
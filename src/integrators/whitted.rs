@@ -0,0 +1,105 @@
+use crate::{
+    integrators::{
+        Integrator,
+        utils::{first_hit, sky_color},
+    },
+    interval::Interval,
+    ray::Ray,
+    renderables::{RayData, Renderable},
+    samplers::Sampler,
+    vec3::Vec3,
+};
+
+/// Classic recursive ray tracer.
+///
+/// Diffuse surfaces are lit directly by directional lights,
+/// with hard shadows and a constant ambient term. Shadows are
+/// lighter behind transparent surfaces and thin volumes.
+/// Only specular materials, like metal and glass, spawn further
+/// rays. It's fast and misses indirect light. Diffuse surfaces
+/// are noise free, but specular ones still scatter by the sampler
+/// (glass picks reflection or refraction, fuzzy metal jitters),
+/// so they need a few samples per pixel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Whitted {
+    pub max_depth: usize,
+    /// Directions towards the lights with their colors.
+    pub lights: Vec<(Vec3, Vec3)>,
+    pub ambient: Vec3,
+}
+
+impl Whitted {
+    /// Starts with a single white light shining from above.
+    #[must_use]
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            lights: vec![(Vec3::new(1., 2., 1.).normalize(), Vec3::ONE)],
+            ambient: Vec3::splat(0.1),
+        }
+    }
+
+    /// Replaces the default light.
+    #[must_use]
+    pub fn with_lights(mut self, lights: Vec<(Vec3, Vec3)>) -> Self {
+        self.lights = lights
+            .into_iter()
+            .map(|(direction, color)| (direction.normalize(), color))
+            .collect();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_ambient(mut self, ambient: Vec3) -> Self {
+        self.ambient = ambient;
+        self
+    }
+
+    fn trace(
+        &self,
+        ray: Ray,
+        depth: usize,
+        scene: &dyn Renderable,
+        sampler: &mut dyn Sampler,
+    ) -> Vec3 {
+        if depth == 0 {
+            return Vec3::ZERO;
+        }
+        let sample = sampler.bounce(self.max_depth - depth);
+        let Some(hit) = first_hit(ray, scene, sample.opacity) else {
+            return sky_color(&ray);
+        };
+        let material = &hit.material_ref;
+        let emitted = material.emitted(&hit);
+
+        if material.is_specular(&hit) {
+            let Some(mut record) = material.scatter_sampled(&ray, &hit, &sample) else {
+                return emitted;
+            };
+            // Once a ray was split by wavelength,
+            // it carries this wavelength till the end of its path.
+            record.ray.wavelength = record.ray.wavelength.or(ray.wavelength);
+            return emitted
+                + record.attenuation * self.trace(record.ray, depth - 1, scene, sampler);
+        }
+
+        let direct: Vec3 = self
+            .lights
+            .iter()
+            .map(|(direction, color)| {
+                let shadow = RayData {
+                    ray: Ray::new(hit.point, *direction),
+                    interval: Interval::new(0.001, f32::INFINITY),
+                };
+                *color * hit.normal.dot(*direction).max(0.) * scene.transmittance(&shadow)
+            })
+            .sum();
+        emitted + material.albedo(&hit) * (self.ambient + direct)
+    }
+}
+
+impl Integrator for Whitted {
+    fn color(&self, ray: Ray, scene: &dyn Renderable, sampler: &mut dyn Sampler) -> Vec3 {
+        self.trace(ray, self.max_depth, scene, sampler)
+    }
+}
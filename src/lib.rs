@@ -24,4 +24,13 @@ pub mod ray;
 pub mod camera;
 pub mod renderables;
 pub mod interval;
+pub mod polynomial;
 pub mod materials;
+pub mod textures;
+pub mod aov;
+pub mod framebuffer;
+pub mod denoise;
+pub mod progressive;
+pub mod samplers;
+pub mod filter;
+pub mod integrators;
@@ -1,12 +1,21 @@
-use std::sync::Arc;
+use std::{
+    iter::Sum,
+    ops::{Div, Mul},
+    sync::Arc,
+};
 
-use glam::Vec3;
+use rand::Rng;
 
-use crate::materials::{Material, MaterialRecord};
+use crate::{materials::Material, samplers::BounceSample};
 
+/// Stochastic mix of any number of materials.
+///
+/// On every scatter only one of the materials is picked
+/// with probability proportional to its weight. On average
+/// it gives weighted sum of all materials.
 #[derive(Debug, Default)]
 pub struct CombineMaterial {
-    pub materials: Vec<Arc<dyn Material>>,
+    pub materials: Vec<(Arc<dyn Material>, f32)>,
 }
 
 impl CombineMaterial {
@@ -16,30 +25,99 @@ impl CombineMaterial {
     }
 
     #[must_use]
-    pub fn add_material(mut self, material: Arc<dyn Material>) -> Self {
-        self.materials.push(material);
+    pub fn add_material(self, material: Arc<dyn Material>) -> Self {
+        self.add_weighted_material(material, 1.)
+    }
+
+    #[must_use]
+    pub fn add_weighted_material(mut self, material: Arc<dyn Material>, weight: f32) -> Self {
+        self.materials.push((material, weight.max(0.)));
         self
     }
 }
 
+impl CombineMaterial {
+    /// Picks a material by `choice` in `0..1`.
+    ///
+    /// Also returns the choice stretched back to `0..1`
+    /// within the picked material, so it can be used again.
+    fn pick(&self, choice: f32) -> Option<(&Arc<dyn Material>, f32)> {
+        let total: f32 = self.materials.iter().map(|(_, weight)| weight).sum();
+        if total <= 0. {
+            return None;
+        }
+        let mut choice = choice * total;
+        for (material, weight) in &self.materials {
+            if choice < *weight {
+                return Some((material, choice / weight));
+            }
+            choice -= weight;
+        }
+        // Because of rounding we might get past the last one.
+        self.materials
+            .iter()
+            .rev()
+            .find(|(_, weight)| *weight > 0.)
+            .map(|(material, _)| (material, 1. - f32::EPSILON))
+    }
+
+    /// Weighted average of a property over all materials.
+    /// `None` if there is nothing to average.
+    fn average<T>(&self, property: impl Fn(&dyn Material) -> T) -> Option<T>
+    where
+        T: Sum + Mul<f32, Output = T> + Div<f32, Output = T>,
+    {
+        let total: f32 = self.materials.iter().map(|(_, weight)| weight).sum();
+        if total <= 0. {
+            return None;
+        }
+        let sum: T = self
+            .materials
+            .iter()
+            .map(|(material, weight)| property(material.as_ref()) * *weight)
+            .sum();
+        Some(sum / total)
+    }
+}
+
 impl Material for CombineMaterial {
     fn scatter(
         &self,
         ray_in: &crate::ray::Ray,
         hit: &crate::renderables::HitRecord,
     ) -> Option<super::MaterialRecord> {
-        let mut ray = *ray_in;
-        let mut albedo = Vec3::ZERO;
+        let (material, _) = self.pick(rand::rng().random_range(0.0..1.0))?;
+        material.scatter(ray_in, hit)
+    }
+
+    fn scatter_sampled(
+        &self,
+        ray_in: &crate::ray::Ray,
+        hit: &crate::renderables::HitRecord,
+        sample: &BounceSample,
+    ) -> Option<super::MaterialRecord> {
+        let (material, choice) = self.pick(sample.choice)?;
+        material.scatter_sampled(ray_in, hit, &BounceSample { choice, ..*sample })
+    }
 
-        for material in &self.materials {
-            if let Some(mat_hit) = material.scatter(&ray, hit) {
-                albedo += mat_hit.attenuation;
-                ray = mat_hit.ray;
-            }
-        }
-        Some(MaterialRecord::new(
-            albedo / self.materials.len() as f32,
-            ray,
-        ))
+    fn emitted(&self, hit: &crate::renderables::HitRecord) -> crate::vec3::Vec3 {
+        self.average(|material| material.emitted(hit))
+            .unwrap_or_default()
+    }
+
+    fn albedo(&self, hit: &crate::renderables::HitRecord) -> crate::vec3::Vec3 {
+        self.average(|material| material.albedo(hit))
+            .unwrap_or_default()
+    }
+
+    /// Specular if any material that can be picked is.
+    fn is_specular(&self, hit: &crate::renderables::HitRecord) -> bool {
+        self.materials
+            .iter()
+            .any(|(material, weight)| *weight > 0. && material.is_specular(hit))
+    }
+
+    fn opacity(&self, hit: &crate::renderables::HitRecord) -> f32 {
+        self.average(|material| material.opacity(hit)).unwrap_or(1.)
     }
 }
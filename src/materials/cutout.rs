@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::{
+    materials::{Material, MaterialRecord},
+    ray::Ray,
+    renderables::HitRecord,
+    samplers::BounceSample,
+    textures::{IntoTexture, Texture},
+    vec3::Vec3,
+};
+
+/// Makes parts of another material transparent,
+/// so rays pass through them as if there was no surface.
+///
+/// Useful for leaves, fences and other detailed shapes
+/// modeled as textured quads and triangles.
+#[derive(Debug, Clone)]
+pub struct Cutout {
+    pub material: Arc<dyn Material>,
+    /// 0 is fully transparent, 1 is fully opaque.
+    pub opacity: Arc<dyn Texture>,
+}
+
+impl Cutout {
+    #[must_use]
+    pub fn new(material: Arc<dyn Material>, opacity: impl IntoTexture) -> Self {
+        Self {
+            material,
+            opacity: opacity.into_texture(),
+        }
+    }
+}
+
+impl Material for Cutout {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<MaterialRecord> {
+        self.material.scatter(ray_in, hit)
+    }
+
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        self.material.scatter_sampled(ray_in, hit, sample)
+    }
+
+    fn emitted(&self, hit: &HitRecord) -> Vec3 {
+        self.material.emitted(hit)
+    }
+
+    fn albedo(&self, hit: &HitRecord) -> Vec3 {
+        self.material.albedo(hit)
+    }
+
+    fn is_specular(&self, hit: &HitRecord) -> bool {
+        self.material.is_specular(hit)
+    }
+
+    fn opacity(&self, hit: &HitRecord) -> f32 {
+        self.opacity.scalar(hit.uv, hit.point) * self.material.opacity(hit)
+    }
+}
@@ -1,56 +1,226 @@
-use glam::Vec3;
+use std::sync::LazyLock;
+
+use glam::{Mat3, Vec2, Vec3};
 use rand::Rng;
 
-use crate::ray::Ray;
+use crate::{
+    materials::utils::reflectance, ray::Ray, renderables::HitRecord, samplers::BounceSample,
+};
+
+/// Visible spectrum range in nanometers.
+const MIN_WAVELENGTH: f32 = 380.;
+const MAX_WAVELENGTH: f32 = 730.;
+
+/// Wavelength-dependent refraction index.
+///
+/// All formulas take wavelength in micrometers,
+/// as most of the published coefficients are for them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dispersion {
+    /// n = A + B / λ^2
+    Cauchy { a: f32, b: f32 },
+    /// n^2 = 1 + Σ `B_i` * λ^2 / (λ^2 - `C_i`)
+    Sellmeier { b: [f32; 3], c: [f32; 3] },
+}
+
+impl Dispersion {
+    /// Borosilicate crown glass. The most common optical glass.
+    pub const BK7: Self = Self::Sellmeier {
+        b: [1.039_612, 0.231_792_34, 1.010_469_5],
+        c: [0.006_000_699, 0.020_017_914, 103.560_65],
+    };
+    /// Dense flint glass. It has much stronger dispersion.
+    pub const SF11: Self = Self::Sellmeier {
+        b: [1.737_596_9, 0.313_747_35, 1.898_781],
+        c: [0.013_188_707, 0.062_306_814, 155.236_3],
+    };
+
+    #[must_use]
+    pub fn refraction_index(&self, wavelength: f32) -> f32 {
+        let micrometers = wavelength / 1000.;
+        let l2 = micrometers * micrometers;
+        match self {
+            Self::Cauchy { a, b } => a + b / l2,
+            Self::Sellmeier { b, c } => {
+                let sum: f32 = b.iter().zip(c).map(|(b, c)| b * l2 / (l2 - c)).sum();
+                (1. + sum).sqrt()
+            }
+        }
+    }
+}
+
+#[rustfmt::skip]
+const XYZ_TO_LINEAR_SRGB: Mat3 = Mat3::from_cols_array(&[
+    3.240_6, -0.968_9, 0.055_7,
+    -1.537_2, 1.875_8, -0.204,
+    -0.498_6, 0.041_5, 1.057,
+]);
+
+/// Piecewise gaussian from the CIE matching functions fit.
+fn lobe(x: f32, mean: f32, sigma_low: f32, sigma_high: f32) -> f32 {
+    let sigma = if x < mean { sigma_low } else { sigma_high };
+    let t = (x - mean) / sigma;
+    (-0.5 * t * t).exp()
+}
+
+/// Linear sRGB color of a single wavelength.
+///
+/// It uses analytic fit of CIE 1931 color matching functions
+/// by Wyman, Sloan and Shirley. Negative values
+/// are clamped as we cannot show them anyway.
+fn wavelength_to_linear_rgb(wavelength: f32) -> Vec3 {
+    let x = Vec3::new(1.056, 0.362, -0.065).dot(Vec3::new(
+        lobe(wavelength, 599.8, 37.9, 31.0),
+        lobe(wavelength, 442.0, 16.0, 26.7),
+        lobe(wavelength, 501.1, 20.4, 26.2),
+    ));
+    let y = Vec2::new(0.821, 0.286).dot(Vec2::new(
+        lobe(wavelength, 568.8, 46.9, 40.5),
+        lobe(wavelength, 530.9, 16.3, 31.1),
+    ));
+    let z = Vec2::new(1.217, 0.681).dot(Vec2::new(
+        lobe(wavelength, 437.0, 11.8, 36.0),
+        lobe(wavelength, 459.0, 26.0, 13.8),
+    ));
+    (XYZ_TO_LINEAR_SRGB * Vec3::new(x, y, z)).max(Vec3::ZERO)
+}
+
+/// Scale for wavelength colors, so that uniformly
+/// sampled wavelengths average to white.
+static SPECTRUM_SCALE: LazyLock<Vec3> = LazyLock::new(|| {
+    let steps = 1000;
+    let step = (MAX_WAVELENGTH - MIN_WAVELENGTH) / steps as f32;
+    let sum: Vec3 = (0..steps)
+        .map(|i| wavelength_to_linear_rgb((i as f32 + 0.5).mul_add(step, MIN_WAVELENGTH)))
+        .sum();
+    steps as f32 / sum
+});
 
 #[derive(Debug)]
 pub struct Dielectric {
     pub refraction_index: f32,
+    /// Absorption coefficient per unit of distance
+    /// traveled inside of the material.
+    pub absorption: Vec3,
+    pub dispersion: Option<Dispersion>,
 }
 
 impl Dielectric {
     #[must_use] 
     pub const fn new(refraction_index: f32) -> Self {
-        Self { refraction_index }
+        Self {
+            refraction_index,
+            absorption: Vec3::ZERO,
+            dispersion: None,
+        }
     }
-}
 
-#[inline]
-/// Schlick's approximation for reflectance.
-fn reflectance(cosine: f32, refraction_index: f32) -> f32 {
-    let r0 = ((1. - refraction_index) / (1. + refraction_index)).powi(2);
-    (1. - r0).mul_add((1. - cosine).powi(5), r0)
+    #[must_use]
+    pub const fn with_absorption(mut self, absorption: Vec3) -> Self {
+        self.absorption = absorption;
+        self
+    }
+
+    /// Sets absorption so that light becomes `color`
+    /// after traveling `distance` inside of the material.
+    ///
+    /// # Panics
+    ///
+    /// If `distance` is not positive.
+    #[must_use]
+    pub fn with_tint(mut self, color: Vec3, distance: f32) -> Self {
+        assert!(distance > 0., "Tint distance must be positive");
+        self.absorption = -color.max(Vec3::splat(f32::MIN_POSITIVE)).map(f32::ln) / distance;
+        self
+    }
+
+    /// Refraction index of dispersive materials depends on wavelength.
+    /// The `refraction_index` field is ignored in that case.
+    #[must_use]
+    pub const fn with_dispersion(mut self, dispersion: Dispersion) -> Self {
+        self.dispersion = Some(dispersion);
+        self
+    }
 }
 
-impl super::Material for Dielectric {
-    fn scatter(
+impl Dielectric {
+    /// `choice` picks between reflection and refraction,
+    /// `spectrum` picks the wavelength for dispersion.
+    fn scatter_with(
         &self,
-        ray_in: &crate::ray::Ray,
-        hit: &crate::renderables::HitRecord,
-    ) -> Option<super::MaterialRecord> {
-        let attenutation = Vec3::ONE;
+        ray_in: &Ray,
+        hit: &HitRecord,
+        choice: f32,
+        spectrum: f32,
+    ) -> super::MaterialRecord {
+        let mut attenutation = Vec3::ONE;
+        let mut wavelength = ray_in.wavelength;
+
+        let refraction_index = self.dispersion.map_or(self.refraction_index, |dispersion| {
+            // Only one wavelength can go through dispersive material.
+            // If the ray is still white, we pick one and keep only its color.
+            let lambda = *wavelength.get_or_insert_with(|| {
+                let lambda = (MAX_WAVELENGTH - MIN_WAVELENGTH).mul_add(spectrum, MIN_WAVELENGTH);
+                attenutation = wavelength_to_linear_rgb(lambda) * *SPECTRUM_SCALE;
+                lambda
+            });
+            dispersion.refraction_index(lambda)
+        });
+
+        // We hit the surface from inside, so the ray
+        // was traveling through the medium. Beer-Lambert law.
+        if !hit.front_face {
+            let distance = hit.distance * ray_in.direction.length();
+            attenutation *= (-self.absorption * distance).exp();
+        }
+
         let ri = if hit.front_face {
-            1.0 / self.refraction_index
+            1.0 / refraction_index
         } else {
-            self.refraction_index
+            refraction_index
         };
         let unit_direction = ray_in.direction.normalize();
         let cos_theta = hit.normal.dot(-unit_direction).min(1.0);
         let sin_theta = cos_theta.mul_add(-cos_theta, 1.).sqrt();
-        let mut rng = rand::rng();
 
         // We cannot refract, because there's no
         // solution for snell's law for this ray.
         // Falling back to reflections.
-        let direction =
-            if ri * sin_theta > 1.0 || reflectance(cos_theta, ri) > rng.random_range(0.0..1.0) {
-                unit_direction.reflect(hit.normal)
-            } else {
-                unit_direction.refract(hit.normal, ri)
-            };
-        Some(super::MaterialRecord::new(
+        let direction = if ri * sin_theta > 1.0 || reflectance(cos_theta, ri) > choice {
+            unit_direction.reflect(hit.normal)
+        } else {
+            unit_direction.refract(hit.normal, ri)
+        };
+        super::MaterialRecord::new(
             attenutation,
-            Ray::new(hit.point, direction),
+            Ray::new(hit.point, direction).with_wavelength(wavelength),
+        )
+    }
+}
+
+impl super::Material for Dielectric {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<super::MaterialRecord> {
+        let mut rng = rand::rng();
+        Some(self.scatter_with(
+            ray_in,
+            hit,
+            rng.random_range(0.0..1.0),
+            rng.random_range(0.0..1.0),
         ))
     }
+
+    /// Direction of the sample isn't needed for
+    /// specular surfaces, so it picks the wavelength.
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<super::MaterialRecord> {
+        Some(self.scatter_with(ray_in, hit, sample.choice, sample.direction.x))
+    }
+
+    fn is_specular(&self, _hit: &HitRecord) -> bool {
+        true
+    }
 }
@@ -1,15 +1,38 @@
+use std::sync::Arc;
+
+use crate::textures::{IntoTexture, Texture};
 use crate::vec3::Vec3Ext;
-use crate::{materials::MaterialRecord, ray::Ray, vec3::Vec3};
+use crate::{
+    materials::{MaterialRecord, utils::sphere_direction},
+    ray::Ray,
+    renderables::HitRecord,
+    samplers::BounceSample,
+    vec3::Vec3,
+};
 
 #[derive(Debug)]
 pub struct Lambertian {
-    albedo: Vec3,
+    albedo: Arc<dyn Texture>,
+}
+
+impl Lambertian {
+    #[must_use]
+    pub fn new(albedo: impl IntoTexture) -> Self {
+        Self {
+            albedo: albedo.into_texture(),
+        }
+    }
 }
 
 impl Lambertian {
-    #[must_use] 
-    pub const fn new(albedo: Vec3) -> Self {
-        Self { albedo }
+    /// Unit vector added to the normal gives cosine-weighted direction.
+    fn scatter_towards(&self, hit: &HitRecord, unit: Vec3) -> MaterialRecord {
+        let mut scattered_direction = hit.normal + unit;
+        if scattered_direction.near_zero() {
+            scattered_direction = hit.normal;
+        }
+        let scattered_ray = Ray::new(hit.point, scattered_direction);
+        MaterialRecord::new(self.albedo.value(hit.uv, hit.point), scattered_ray)
     }
 }
 
@@ -19,12 +42,19 @@ impl super::Material for Lambertian {
         _ray_in: &crate::ray::Ray,
         hit: &crate::renderables::HitRecord,
     ) -> Option<super::MaterialRecord> {
-        let mut rng = rand::rng();
-        let mut scattered_direction = hit.normal + Vec3::rand_unit(&mut rng);
-        if scattered_direction.near_zero() {
-            scattered_direction = hit.normal;
-        }
-        let scattered_ray = Ray::new(hit.point, scattered_direction);
-        Some(MaterialRecord::new(self.albedo, scattered_ray))
+        Some(self.scatter_towards(hit, Vec3::rand_unit(&mut rand::rng())))
+    }
+
+    fn scatter_sampled(
+        &self,
+        _ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        Some(self.scatter_towards(hit, sphere_direction(sample.direction)))
+    }
+
+    fn albedo(&self, hit: &crate::renderables::HitRecord) -> Vec3 {
+        self.albedo.value(hit.uv, hit.point)
     }
 }
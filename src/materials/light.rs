@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use crate::{
+    materials::{Material, MaterialRecord},
+    ray::Ray,
+    renderables::HitRecord,
+    textures::{IntoTexture, Texture},
+    vec3::Vec3,
+};
+
+/// Emits light and doesn't reflect anything.
+///
+/// By default only front side of the surface emits.
+#[derive(Debug, Clone)]
+pub struct DiffuseLight {
+    pub emission: Arc<dyn Texture>,
+    pub two_sided: bool,
+}
+
+impl DiffuseLight {
+    #[must_use]
+    pub fn new(emission: impl IntoTexture) -> Self {
+        Self {
+            emission: emission.into_texture(),
+            two_sided: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_two_sided(mut self, two_sided: bool) -> Self {
+        self.two_sided = two_sided;
+        self
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _ray_in: &Ray, _hit: &HitRecord) -> Option<MaterialRecord> {
+        None
+    }
+
+    fn emitted(&self, hit: &HitRecord) -> Vec3 {
+        if hit.front_face || self.two_sided {
+            self.emission.value(hit.uv, hit.point)
+        } else {
+            Vec3::ZERO
+        }
+    }
+}
@@ -1,19 +1,27 @@
+use std::sync::Arc;
+
 use crate::{
-    materials::Material,
+    materials::{Material, MaterialRecord, utils::sphere_direction},
     ray::Ray,
+    renderables::HitRecord,
+    samplers::BounceSample,
+    textures::{IntoTexture, Texture},
     vec3::{Vec3, Vec3Ext},
 };
 
 #[derive(Debug, Clone)]
 pub struct Metal {
-    pub albedo: Vec3,
+    pub albedo: Arc<dyn Texture>,
     pub fuzz: f32,
 }
 
 impl Metal {
-    #[must_use] 
-    pub const fn new(albedo: Vec3) -> Self {
-        Self { albedo, fuzz: 1. }
+    #[must_use]
+    pub fn new(albedo: impl IntoTexture) -> Self {
+        Self {
+            albedo: albedo.into_texture(),
+            fuzz: 1.,
+        }
     }
 
     #[must_use] 
@@ -27,18 +35,14 @@ impl Metal {
     }
 }
 
-impl Material for Metal {
-    fn scatter(
-        &self,
-        ray_in: &crate::ray::Ray,
-        hit: &crate::renderables::HitRecord,
-    ) -> Option<super::MaterialRecord> {
-        let mut rng = rand::rng();
+impl Metal {
+    /// Mirror reflection, shifted by fuzz in the direction of `unit`.
+    fn reflect(&self, ray_in: &Ray, hit: &HitRecord, unit: Vec3) -> Option<MaterialRecord> {
         let mut reflection = ray_in.direction.reflect(hit.normal);
-        reflection = reflection.normalize() + (self.fuzz * Vec3::rand_unit(&mut rng));
+        reflection = reflection.normalize() + (self.fuzz * unit);
         if reflection.dot(hit.normal) > 0. {
-            Some(super::MaterialRecord::new(
-                self.albedo,
+            Some(MaterialRecord::new(
+                self.albedo.value(hit.uv, hit.point),
                 Ray::new(hit.point, reflection),
             ))
         } else {
@@ -46,3 +50,30 @@ impl Material for Metal {
         }
     }
 }
+
+impl Material for Metal {
+    fn scatter(
+        &self,
+        ray_in: &crate::ray::Ray,
+        hit: &crate::renderables::HitRecord,
+    ) -> Option<super::MaterialRecord> {
+        self.reflect(ray_in, hit, Vec3::rand_unit(&mut rand::rng()))
+    }
+
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        self.reflect(ray_in, hit, sphere_direction(sample.direction))
+    }
+
+    fn albedo(&self, hit: &crate::renderables::HitRecord) -> Vec3 {
+        self.albedo.value(hit.uv, hit.point)
+    }
+
+    fn is_specular(&self, _hit: &HitRecord) -> bool {
+        true
+    }
+}
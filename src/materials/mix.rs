@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::{
+    materials::{Material, MaterialRecord, utils::reflectance},
+    ray::Ray,
+    renderables::HitRecord,
+    samplers::BounceSample,
+    textures::{IntoTexture, Texture},
+    vec3::Vec3,
+};
+
+/// Defines how often the second material is picked.
+#[derive(Debug, Clone)]
+pub enum MixFactor {
+    /// Constant or texture-driven weight.
+    Texture(Arc<dyn Texture>),
+    /// Weight is the Fresnel reflectance of a dielectric
+    /// with given IOR, so the second material shows up
+    /// at grazing angles. Good for varnish and coated plastic.
+    Fresnel(f32),
+}
+
+/// Blends two materials.
+///
+/// It doesn't average them, but picks one
+/// on each scatter, so the result is a valid material.
+#[derive(Debug, Clone)]
+pub struct MixMaterial {
+    pub first: Arc<dyn Material>,
+    pub second: Arc<dyn Material>,
+    pub factor: MixFactor,
+}
+
+impl MixMaterial {
+    #[must_use]
+    pub fn new(
+        first: Arc<dyn Material>,
+        second: Arc<dyn Material>,
+        factor: impl IntoTexture,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            factor: MixFactor::Texture(factor.into_texture()),
+        }
+    }
+
+    /// Layers the coat on top of the base
+    /// using Fresnel reflectance as a factor.
+    #[must_use]
+    pub const fn fresnel(base: Arc<dyn Material>, coat: Arc<dyn Material>, ior: f32) -> Self {
+        Self {
+            first: base,
+            second: coat,
+            factor: MixFactor::Fresnel(ior),
+        }
+    }
+}
+
+/// Weight read from a texture. It's limited to `0..1`,
+/// so the materials are blended and never extrapolated.
+fn texture_factor(texture: &dyn Texture, hit: &HitRecord) -> f32 {
+    texture.scalar(hit.uv, hit.point).clamp(0., 1.)
+}
+
+impl MixMaterial {
+    /// Chance to pick the second material.
+    fn factor(&self, ray_in: &Ray, hit: &HitRecord) -> f32 {
+        match &self.factor {
+            MixFactor::Texture(texture) => texture_factor(texture.as_ref(), hit),
+            MixFactor::Fresnel(ior) => {
+                let cos_theta = hit.normal.dot(-ray_in.direction.normalize()).min(1.);
+                let ri = if hit.front_face { 1. / ior } else { *ior };
+                reflectance(cos_theta, ri)
+            }
+        }
+    }
+
+    /// Factor for properties which don't depend on the ray.
+    fn surface_factor(&self, hit: &HitRecord) -> f32 {
+        match &self.factor {
+            MixFactor::Texture(texture) => texture_factor(texture.as_ref(), hit),
+            // Coat is usually clear, so the base is what we see.
+            MixFactor::Fresnel(_) => 0.,
+        }
+    }
+}
+
+impl Material for MixMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<MaterialRecord> {
+        if rand::rng().random_range(0.0..1.0) < self.factor(ray_in, hit) {
+            self.second.scatter(ray_in, hit)
+        } else {
+            self.first.scatter(ray_in, hit)
+        }
+    }
+
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        let factor = self.factor(ray_in, hit);
+        // Choice is stretched back to 0..1, so the picked
+        // material can make its own choice with it.
+        if sample.choice < factor {
+            let sample = BounceSample {
+                choice: sample.choice / factor,
+                ..*sample
+            };
+            self.second.scatter_sampled(ray_in, hit, &sample)
+        } else {
+            let sample = BounceSample {
+                choice: (sample.choice - factor) / (1. - factor),
+                ..*sample
+            };
+            self.first.scatter_sampled(ray_in, hit, &sample)
+        }
+    }
+
+    fn emitted(&self, hit: &HitRecord) -> Vec3 {
+        let factor = self.surface_factor(hit);
+        self.first
+            .emitted(hit)
+            .lerp(self.second.emitted(hit), factor)
+    }
+
+    fn albedo(&self, hit: &HitRecord) -> Vec3 {
+        let factor = self.surface_factor(hit);
+        self.first.albedo(hit).lerp(self.second.albedo(hit), factor)
+    }
+
+    /// Specular if any material that can be picked is.
+    fn is_specular(&self, hit: &HitRecord) -> bool {
+        let (first, second) = match &self.factor {
+            MixFactor::Texture(texture) => {
+                let factor = texture_factor(texture.as_ref(), hit);
+                (factor < 1., factor > 0.)
+            }
+            // Fresnel weight is never zero or one.
+            MixFactor::Fresnel(_) => (true, true),
+        };
+        (first && self.first.is_specular(hit)) || (second && self.second.is_specular(hit))
+    }
+
+    fn opacity(&self, hit: &HitRecord) -> f32 {
+        let first = self.first.opacity(hit);
+        (self.second.opacity(hit) - first).mul_add(self.surface_factor(hit), first)
+    }
+}
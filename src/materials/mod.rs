@@ -4,10 +4,23 @@ mod traits;
 mod utils;
 mod dielectric;
 mod combine;
+mod cutout;
+mod light;
+mod mix;
+mod normal_map;
+mod phase;
+mod principled;
 
 pub use lambertian::Lambertian;
 pub use metal::Metal;
 pub use traits::Material;
 pub use utils::MaterialRecord;
-pub use dielectric::Dielectric;
+pub use dielectric::{Dielectric, Dispersion};
 pub use combine::CombineMaterial;
+pub use cutout::Cutout;
+pub use mix::{MixFactor, MixMaterial};
+pub use normal_map::{NormalMapped, Perturbation};
+pub use principled::Principled;
+pub use phase::{HenyeyGreenstein, Isotropic};
+pub use light::DiffuseLight;
+pub(crate) use utils::sphere_direction;
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use crate::{
+    materials::{Material, MaterialRecord},
+    ray::Ray,
+    renderables::HitRecord,
+    samplers::BounceSample,
+    textures::{IntoTexture, Texture},
+    vec3::Vec3,
+};
+
+/// Step in UV used to find the slope of a bump map.
+const BUMP_DELTA: f32 = 1e-3;
+
+/// How the surface normal is changed.
+#[derive(Debug, Clone)]
+pub enum Perturbation {
+    /// Tangent-space normal encoded as color in `0..1`,
+    /// where blue points along the original normal.
+    Normal(Arc<dyn Texture>),
+    /// Height of the surface. Normal follows its slope.
+    Bump(Arc<dyn Texture>),
+}
+
+/// Adds surface detail to another material
+/// by changing the normal before scattering.
+///
+/// It doesn't change the geometry, so silhouettes stay smooth.
+/// The tangent frame comes from the UV of the hit.
+#[derive(Debug, Clone)]
+pub struct NormalMapped {
+    pub material: Arc<dyn Material>,
+    pub perturbation: Perturbation,
+    pub strength: f32,
+}
+
+impl NormalMapped {
+    #[must_use]
+    pub fn normal_map(material: Arc<dyn Material>, map: impl IntoTexture) -> Self {
+        Self {
+            material,
+            perturbation: Perturbation::Normal(map.into_texture()),
+            strength: 1.,
+        }
+    }
+
+    #[must_use]
+    pub fn bump_map(material: Arc<dyn Material>, height: impl IntoTexture) -> Self {
+        Self {
+            material,
+            perturbation: Perturbation::Bump(height.into_texture()),
+            strength: 1.,
+        }
+    }
+
+    /// Scales the effect. For bump maps it's the height
+    /// of the texture in UV units.
+    #[must_use]
+    pub const fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    fn perturb(&self, hit: &HitRecord) -> HitRecord {
+        let (tangent, bitangent, normal) = hit.tangent_frame();
+        let local = match &self.perturbation {
+            Perturbation::Normal(map) => {
+                let value = map.value(hit.uv, hit.point) * 2. - 1.;
+                Vec3::new(value.x * self.strength, value.y * self.strength, value.z)
+            }
+            Perturbation::Bump(height) => {
+                let du = tangent * BUMP_DELTA;
+                let dv = bitangent * BUMP_DELTA;
+                let center = height.scalar(hit.uv, hit.point);
+                let along_u = height.scalar(hit.uv + glam::Vec2::X * BUMP_DELTA, hit.point + du);
+                let along_v = height.scalar(hit.uv + glam::Vec2::Y * BUMP_DELTA, hit.point + dv);
+                Vec3::new(
+                    -(along_u - center) / BUMP_DELTA * self.strength,
+                    -(along_v - center) / BUMP_DELTA * self.strength,
+                    1.,
+                )
+            }
+        };
+
+        let mut hit = hit.clone();
+        let perturbed =
+            (tangent * local.x + bitangent * local.y + normal * local.z).try_normalize();
+        // Normals facing away from the surface would send rays inside.
+        if let Some(perturbed) = perturbed.filter(|n| n.dot(normal) > 0.) {
+            hit.normal = perturbed;
+        }
+        hit
+    }
+}
+
+impl Material for NormalMapped {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<MaterialRecord> {
+        self.material.scatter(ray_in, &self.perturb(hit))
+    }
+
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        self.material.scatter_sampled(ray_in, &self.perturb(hit), sample)
+    }
+
+    fn emitted(&self, hit: &HitRecord) -> Vec3 {
+        self.material.emitted(hit)
+    }
+
+    fn albedo(&self, hit: &HitRecord) -> Vec3 {
+        self.material.albedo(hit)
+    }
+
+    fn is_specular(&self, hit: &HitRecord) -> bool {
+        self.material.is_specular(hit)
+    }
+
+    fn opacity(&self, hit: &HitRecord) -> f32 {
+        self.material.opacity(hit)
+    }
+}
@@ -0,0 +1,139 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::Vec2;
+use rand::Rng;
+
+use crate::{
+    materials::{Material, MaterialRecord, utils::sphere_direction},
+    ray::Ray,
+    renderables::HitRecord,
+    samplers::BounceSample,
+    textures::{IntoTexture, Texture},
+    vec3::{Vec3, Vec3Ext},
+};
+
+/// Phase function of a medium that scatters
+/// light equally in all directions.
+#[derive(Debug, Clone)]
+pub struct Isotropic {
+    pub albedo: Arc<dyn Texture>,
+    pub emission: Option<Arc<dyn Texture>>,
+}
+
+impl Isotropic {
+    #[must_use]
+    pub fn new(albedo: impl IntoTexture) -> Self {
+        Self {
+            albedo: albedo.into_texture(),
+            emission: None,
+        }
+    }
+
+    /// Makes medium glow, like fire or hot gas.
+    #[must_use]
+    pub fn with_emission(mut self, emission: impl IntoTexture) -> Self {
+        self.emission = Some(emission.into_texture());
+        self
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(&self, _ray_in: &Ray, hit: &HitRecord) -> Option<MaterialRecord> {
+        let direction = Vec3::rand_unit(&mut rand::rng());
+        Some(MaterialRecord::new(
+            self.albedo.value(hit.uv, hit.point),
+            Ray::new(hit.point, direction),
+        ))
+    }
+
+    fn scatter_sampled(
+        &self,
+        _ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        Some(MaterialRecord::new(
+            self.albedo.value(hit.uv, hit.point),
+            Ray::new(hit.point, sphere_direction(sample.direction)),
+        ))
+    }
+
+    fn albedo(&self, hit: &HitRecord) -> Vec3 {
+        self.albedo.value(hit.uv, hit.point)
+    }
+
+    fn emitted(&self, hit: &HitRecord) -> Vec3 {
+        self.emission
+            .as_ref()
+            .map_or(Vec3::ZERO, |emission| emission.value(hit.uv, hit.point))
+    }
+}
+
+/// Henyey-Greenstein phase function.
+///
+/// Asymmetry `g` is in range (-1, 1). Positive values scatter
+/// light forward (fog, clouds), negative ones back to the source.
+/// Zero is the same as `Isotropic`.
+#[derive(Debug, Clone)]
+pub struct HenyeyGreenstein {
+    pub albedo: Arc<dyn Texture>,
+    pub g: f32,
+}
+
+impl HenyeyGreenstein {
+    #[must_use]
+    pub fn new(albedo: impl IntoTexture, g: f32) -> Self {
+        Self {
+            albedo: albedo.into_texture(),
+            g: g.clamp(-0.999, 0.999),
+        }
+    }
+}
+
+impl HenyeyGreenstein {
+    /// Direction picked by a point of unit square.
+    fn scatter_towards(&self, ray_in: &Ray, hit: &HitRecord, sample: Vec2) -> MaterialRecord {
+        let g = self.g;
+        // Inverted CDF of HG for the angle between
+        // incoming and scattered directions.
+        let cos_theta = if g.abs() < 1e-3 {
+            2.0f32.mul_add(-sample.x, 1.)
+        } else {
+            let sq = g.mul_add(-g, 1.) / (2. * g).mul_add(sample.x, 1. - g);
+            sq.mul_add(-sq, g.mul_add(g, 1.)) / (2. * g)
+        };
+        let sin_theta = cos_theta.mul_add(-cos_theta, 1.).max(0.).sqrt();
+        let phi = 2. * PI * sample.y;
+
+        let forward = ray_in.direction.normalize();
+        let (tangent, bitangent) = forward.any_orthonormal_pair();
+        let direction = tangent * (sin_theta * phi.cos())
+            + bitangent * (sin_theta * phi.sin())
+            + forward * cos_theta;
+        MaterialRecord::new(
+            self.albedo.value(hit.uv, hit.point),
+            Ray::new(hit.point, direction),
+        )
+    }
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<MaterialRecord> {
+        let mut rng = rand::rng();
+        let sample = Vec2::new(rng.random_range(0.0..1.0), rng.random_range(0.0..1.0));
+        Some(self.scatter_towards(ray_in, hit, sample))
+    }
+
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        Some(self.scatter_towards(ray_in, hit, sample.direction))
+    }
+
+    fn albedo(&self, hit: &HitRecord) -> Vec3 {
+        self.albedo.value(hit.uv, hit.point)
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use glam::Vec2;
+use rand::Rng;
+
+use crate::{
+    materials::{
+        Material, MaterialRecord,
+        utils::{reflectance, sample_ggx_normal, schlick, schlick_color, sphere_direction},
+    },
+    ray::Ray,
+    renderables::HitRecord,
+    samplers::BounceSample,
+    textures::{IntoTexture, Texture},
+    vec3::{Vec3, Vec3Ext},
+};
+
+/// Reflectance of a clearcoat layer at normal incidence.
+/// It corresponds to a polyurethane varnish with IOR of 1.5.
+const CLEARCOAT_R0: f32 = 0.04;
+
+/// Disney-style principled material.
+///
+/// All the parameters except IOR are in range [0, 1]
+/// and can be driven by textures.
+///
+/// Instead of evaluating all lobes at once, on each scatter
+/// we randomly pick one of them with probability
+/// equal to its weight. This way the attenuation of each
+/// lobe is just its color and the whole thing stays energy conserving.
+#[derive(Debug, Clone)]
+pub struct Principled {
+    pub base_color: Arc<dyn Texture>,
+    pub metallic: Arc<dyn Texture>,
+    pub roughness: Arc<dyn Texture>,
+    pub specular: Arc<dyn Texture>,
+    pub transmission: Arc<dyn Texture>,
+    pub clearcoat: Arc<dyn Texture>,
+    pub clearcoat_roughness: Arc<dyn Texture>,
+    pub sheen: Arc<dyn Texture>,
+    pub sheen_tint: Arc<dyn Texture>,
+    pub ior: f32,
+}
+
+impl Principled {
+    #[must_use]
+    pub fn new(base_color: impl IntoTexture) -> Self {
+        Self {
+            base_color: base_color.into_texture(),
+            metallic: 0.0.into_texture(),
+            roughness: 0.5.into_texture(),
+            specular: 0.5.into_texture(),
+            transmission: 0.0.into_texture(),
+            clearcoat: 0.0.into_texture(),
+            clearcoat_roughness: 0.03.into_texture(),
+            sheen: 0.0.into_texture(),
+            sheen_tint: 0.5.into_texture(),
+            ior: 1.45,
+        }
+    }
+
+    #[must_use]
+    pub fn with_metallic(mut self, metallic: impl IntoTexture) -> Self {
+        self.metallic = metallic.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub fn with_roughness(mut self, roughness: impl IntoTexture) -> Self {
+        self.roughness = roughness.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub fn with_specular(mut self, specular: impl IntoTexture) -> Self {
+        self.specular = specular.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub fn with_transmission(mut self, transmission: impl IntoTexture) -> Self {
+        self.transmission = transmission.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub fn with_clearcoat(mut self, clearcoat: impl IntoTexture) -> Self {
+        self.clearcoat = clearcoat.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub fn with_clearcoat_roughness(mut self, roughness: impl IntoTexture) -> Self {
+        self.clearcoat_roughness = roughness.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub fn with_sheen(mut self, sheen: impl IntoTexture) -> Self {
+        self.sheen = sheen.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub fn with_sheen_tint(mut self, sheen_tint: impl IntoTexture) -> Self {
+        self.sheen_tint = sheen_tint.into_texture();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_ior(mut self, ior: f32) -> Self {
+        self.ior = ior;
+        self
+    }
+}
+
+/// Reflects the ray around microfacet normal sampled
+/// from GGX distribution. Returns `None` if the
+/// reflected ray goes under the surface.
+fn glossy_reflection(sample: Vec2, direction: Vec3, normal: Vec3, roughness: f32) -> Option<Vec3> {
+    let microfacet = sample_ggx_normal(sample, normal, roughness * roughness);
+    let reflected = direction.reflect(microfacet);
+    (reflected.dot(normal) > 0.).then_some(reflected)
+}
+
+/// Takes a decision with given probability, and stretches
+/// the choice back to `0..1`, so it can be used for the next one.
+fn decide(choice: &mut f32, probability: f32) -> bool {
+    let taken = *choice < probability;
+    *choice = if taken {
+        *choice / probability
+    } else {
+        (*choice - probability) / (1. - probability)
+    }
+    .min(1. - f32::EPSILON);
+    taken
+}
+
+impl Principled {
+    /// Picks a lobe by `choice` and its direction by `sample`.
+    fn scatter_with(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        mut choice: f32,
+        sample: Vec2,
+    ) -> Option<MaterialRecord> {
+        let scalar = |texture: &Arc<dyn Texture>| texture.scalar(hit.uv, hit.point).clamp(0., 1.);
+        let base_color = self.base_color.value(hit.uv, hit.point);
+        let metallic = scalar(&self.metallic);
+        let roughness = scalar(&self.roughness);
+        let transmission = scalar(&self.transmission);
+
+        let direction = ray_in.direction.normalize();
+        let cos_theta = hit.normal.dot(-direction).clamp(0., 1.);
+
+        // Clearcoat is a thin colorless layer on top of everything else.
+        let clearcoat = scalar(&self.clearcoat);
+        if hit.front_face
+            && clearcoat > 0.
+            && decide(&mut choice, clearcoat * schlick(cos_theta, CLEARCOAT_R0))
+        {
+            let reflected = glossy_reflection(
+                sample,
+                direction,
+                hit.normal,
+                scalar(&self.clearcoat_roughness),
+            )?;
+            return Some(MaterialRecord::new(
+                Vec3::ONE,
+                Ray::new(hit.point, reflected),
+            ));
+        }
+
+        // Metals have no diffuse part. Their specular reflection
+        // is tinted by the base color.
+        if decide(&mut choice, metallic) {
+            let reflected = glossy_reflection(sample, direction, hit.normal, roughness)?;
+            let attenuation = schlick_color(cos_theta, base_color);
+            return Some(MaterialRecord::new(
+                attenuation,
+                Ray::new(hit.point, reflected),
+            ));
+        }
+
+        // Rough glass. Same as Dielectric, but with microfacets.
+        if decide(&mut choice, transmission) {
+            let microfacet = sample_ggx_normal(sample, hit.normal, roughness * roughness);
+            let ri = if hit.front_face {
+                1. / self.ior
+            } else {
+                self.ior
+            };
+            let cos_m = microfacet.dot(-direction).clamp(0., 1.);
+            let sin_m = cos_m.mul_add(-cos_m, 1.).sqrt();
+            let scattered = if ri * sin_m > 1.0 || decide(&mut choice, reflectance(cos_m, ri)) {
+                direction.reflect(microfacet)
+            } else {
+                direction.refract(microfacet, ri)
+            };
+            return Some(MaterialRecord::new(
+                base_color,
+                Ray::new(hit.point, scattered),
+            ));
+        }
+
+        // Dielectric base. Either specular highlight or diffuse.
+        let specular_r0 = 0.08 * scalar(&self.specular);
+        if decide(&mut choice, schlick(cos_theta, specular_r0))
+            && let Some(reflected) = glossy_reflection(sample, direction, hit.normal, roughness)
+        {
+            return Some(MaterialRecord::new(
+                Vec3::ONE,
+                Ray::new(hit.point, reflected),
+            ));
+        }
+
+        let mut scattered = hit.normal + sphere_direction(sample);
+        if scattered.near_zero() {
+            scattered = hit.normal;
+        }
+        // Sheen adds a soft retro-reflective rim, mostly for cloth.
+        let luminance = base_color.dot(Vec3::new(0.3, 0.6, 0.1));
+        let tint = if luminance > 0. {
+            base_color / luminance
+        } else {
+            Vec3::ONE
+        };
+        let sheen_color = Vec3::ONE.lerp(tint, scalar(&self.sheen_tint));
+        let sheen = (scalar(&self.sheen) * sheen_color * (1. - cos_theta).powi(5)).min(Vec3::ONE);
+        // Sheen only takes the energy which the base doesn't reflect.
+        let remaining = (Vec3::ONE - base_color).max(Vec3::ZERO);
+        Some(MaterialRecord::new(
+            base_color + sheen * remaining,
+            Ray::new(hit.point, scattered),
+        ))
+    }
+}
+
+impl Material for Principled {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<MaterialRecord> {
+        let mut rng = rand::rng();
+        let sample = Vec2::new(rng.random_range(0.0..1.0), rng.random_range(0.0..1.0));
+        self.scatter_with(ray_in, hit, rng.random_range(0.0..1.0), sample)
+    }
+
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        self.scatter_with(ray_in, hit, sample.choice, sample.direction)
+    }
+
+    fn albedo(&self, hit: &HitRecord) -> Vec3 {
+        self.base_color.value(hit.uv, hit.point)
+    }
+
+    /// Metallic and transmissive lobes are specular.
+    fn is_specular(&self, hit: &HitRecord) -> bool {
+        self.metallic.scalar(hit.uv, hit.point) > 0.
+            || self.transmission.scalar(hit.uv, hit.point) > 0.
+    }
+}
@@ -1,7 +1,46 @@
 use std::fmt::Debug;
 
-use crate::{materials::MaterialRecord, ray::Ray, renderables::HitRecord};
+use crate::{
+    materials::MaterialRecord, ray::Ray, renderables::HitRecord, samplers::BounceSample,
+    vec3::Vec3,
+};
 
 pub trait Material: Debug + Send + Sync {
     fn scatter(&self, ray_in: &Ray, hit: &HitRecord) -> Option<MaterialRecord>;
+
+    /// Same as `scatter`, but random decisions are taken from the sample.
+    ///
+    /// Materials which don't support it ignore the sample
+    /// and use their own randoms.
+    fn scatter_sampled(
+        &self,
+        ray_in: &Ray,
+        hit: &HitRecord,
+        _sample: &BounceSample,
+    ) -> Option<MaterialRecord> {
+        self.scatter(ray_in, hit)
+    }
+
+    /// Light emitted by the surface itself.
+    fn emitted(&self, _hit: &HitRecord) -> Vec3 {
+        Vec3::ZERO
+    }
+
+    /// Base color of the surface, as seen by
+    /// denoisers and compositing. It isn't used for rendering.
+    fn albedo(&self, _hit: &HitRecord) -> Vec3 {
+        Vec3::ONE
+    }
+
+    /// Mirror-like materials that are traced by following
+    /// their scattered ray, rather than by lighting.
+    fn is_specular(&self, _hit: &HitRecord) -> bool {
+        false
+    }
+
+    /// Chance that the ray stops at the surface.
+    /// Otherwise it goes through as if there was no hit.
+    fn opacity(&self, _hit: &HitRecord) -> f32 {
+        1.
+    }
 }
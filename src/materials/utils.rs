@@ -1,3 +1,7 @@
+use std::f32::consts::PI;
+
+use glam::Vec2;
+
 use crate::{ray::Ray, vec3::Vec3};
 
 pub struct MaterialRecord {
@@ -11,3 +15,49 @@ impl MaterialRecord {
         Self { attenuation, ray }
     }
 }
+
+#[inline]
+/// Schlick's approximation for reflectance.
+pub fn reflectance(cosine: f32, refraction_index: f32) -> f32 {
+    let r0 = ((1. - refraction_index) / (1. + refraction_index)).powi(2);
+    schlick(cosine, r0)
+}
+
+#[inline]
+/// Schlick's approximation with known reflectance at normal incidence.
+pub fn schlick(cosine: f32, r0: f32) -> f32 {
+    (1. - r0).mul_add((1. - cosine).clamp(0., 1.).powi(5), r0)
+}
+
+#[inline]
+/// Same as `schlick`, but for colored reflectance of metals.
+pub fn schlick_color(cosine: f32, r0: Vec3) -> Vec3 {
+    r0 + (Vec3::ONE - r0) * (1. - cosine).clamp(0., 1.).powi(5)
+}
+
+/// Samples microfacet normal from GGX distribution
+/// around given surface normal.
+///
+/// Alpha is a squared roughness. Zero alpha gives
+/// the normal itself, which is a perfect mirror.
+/// Sample is a point of unit square.
+pub fn sample_ggx_normal(sample: Vec2, normal: Vec3, alpha: f32) -> Vec3 {
+    if alpha <= f32::EPSILON {
+        return normal;
+    }
+    let phi = 2. * PI * sample.x;
+    let cos_theta = ((1. - sample.y) / alpha.mul_add(alpha, -1.).mul_add(sample.y, 1.)).sqrt();
+    let sin_theta = cos_theta.mul_add(-cos_theta, 1.).max(0.).sqrt();
+    let (tangent, bitangent) = normal.any_orthonormal_pair();
+    (tangent * (sin_theta * phi.cos()) + bitangent * (sin_theta * phi.sin()) + normal * cos_theta)
+        .normalize()
+}
+
+/// Maps a point of unit square to a point on unit sphere,
+/// keeping the distribution uniform.
+pub fn sphere_direction(sample: Vec2) -> Vec3 {
+    let z = 2.0f32.mul_add(-sample.x, 1.);
+    let radius = z.mul_add(-z, 1.).max(0.).sqrt();
+    let phi = 2. * PI * sample.y;
+    Vec3::new(radius * phi.cos(), radius * phi.sin(), z)
+}
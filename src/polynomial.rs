@@ -0,0 +1,165 @@
+//! Closed form solutions of polynomial equations.
+//!
+//! Based on "Solving Quartic and Cubic Equations" by Jochen Schwarze
+//! from Graphics Gems I. Everything is done in f64, because
+//! quartics lose precision really fast.
+
+// Math notation is clearer here.
+#![allow(clippy::many_single_char_names)]
+
+use std::f64::consts::PI;
+
+const EPSILON: f64 = 1e-9;
+
+fn is_zero(value: f64) -> bool {
+    value.abs() < EPSILON
+}
+
+/// Real roots of a * x^2 + b * x + c = 0.
+#[must_use]
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if is_zero(a) {
+        if is_zero(b) {
+            return Vec::new();
+        }
+        return vec![-c / b];
+    }
+    // Normal form: x^2 + 2p * x + q = 0
+    let p = b / (2. * a);
+    let q = c / a;
+    let discriminant = p.mul_add(p, -q);
+    if is_zero(discriminant) {
+        vec![-p]
+    } else if discriminant < 0. {
+        Vec::new()
+    } else {
+        let sqrt_d = discriminant.sqrt();
+        vec![-sqrt_d - p, sqrt_d - p]
+    }
+}
+
+/// Real roots of a * x^3 + b * x^2 + c * x + d = 0.
+#[must_use]
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if is_zero(a) {
+        return solve_quadratic(b, c, d);
+    }
+    // Normal form: x^3 + A * x^2 + B * x + C = 0
+    let a_n = b / a;
+    let b_n = c / a;
+    let c_n = d / a;
+
+    // Substitute x = y - A/3 to eliminate quadric term:
+    // y^3 + 3p * y + 2q = 0
+    let sq_a = a_n * a_n;
+    let p = (-sq_a / 3.0 + b_n) / 3.;
+    let q = (2. / 27. * sq_a).mul_add(a_n, (-a_n / 3.).mul_add(b_n, c_n)) / 2.;
+
+    // Cardano's formula
+    let cb_p = p * p * p;
+    let discriminant = q.mul_add(q, cb_p);
+
+    let mut roots = if is_zero(discriminant) {
+        if is_zero(q) {
+            vec![0.]
+        } else {
+            let u = (-q).cbrt();
+            vec![2. * u, -u]
+        }
+    } else if discriminant < 0. {
+        // Casus irreducibilis: three real solutions
+        let phi = (-q / (-cb_p).sqrt()).clamp(-1., 1.).acos() / 3.;
+        let t = 2. * (-p).sqrt();
+        vec![
+            t * phi.cos(),
+            -t * (phi + PI / 3.).cos(),
+            -t * (phi - PI / 3.).cos(),
+        ]
+    } else {
+        let sqrt_d = discriminant.sqrt();
+        let u = (sqrt_d - q).cbrt();
+        let v = -(sqrt_d + q).cbrt();
+        vec![u + v]
+    };
+
+    let sub = a_n / 3.;
+    for root in &mut roots {
+        *root -= sub;
+    }
+    roots
+}
+
+/// Real roots of a * x^4 + b * x^3 + c * x^2 + d * x + e = 0.
+///
+/// Roots are polished with a couple of Newton iterations,
+/// since Ferrari's method is not very precise on its own.
+#[must_use]
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if is_zero(a) {
+        return solve_cubic(b, c, d, e);
+    }
+    // Normal form: x^4 + A * x^3 + B * x^2 + C * x + D = 0
+    let a_n = b / a;
+    let b_n = c / a;
+    let c_n = d / a;
+    let d_n = e / a;
+
+    // Substitute x = y - A/4 to eliminate cubic term:
+    // y^4 + p * y^2 + q * y + r = 0
+    let sq_a = a_n * a_n;
+    let p = (-3. / 8.0f64).mul_add(sq_a, b_n);
+    let q = (sq_a / 8.).mul_add(a_n, (-a_n / 2.).mul_add(b_n, c_n));
+    let r = (-3. / 256. * sq_a).mul_add(
+        sq_a,
+        (sq_a / 16.).mul_add(b_n, (-a_n / 4.).mul_add(c_n, d_n)),
+    );
+
+    let mut roots = if is_zero(r) {
+        // No absolute term: y * (y^3 + p * y + q) = 0
+        let mut roots = solve_cubic(1., 0., p, q);
+        roots.push(0.);
+        roots
+    } else {
+        // Solve the resolvent cubic and take one real root
+        let z = solve_cubic(1., -p / 2., -r, (r * p).mul_add(0.5, -q * q / 8.))[0];
+
+        // ... and use it to build two quadric equations
+        let u = z.mul_add(z, -r);
+        let v = 2.0f64.mul_add(z, -p);
+        let u = if is_zero(u) {
+            0.
+        } else if u > 0. {
+            u.sqrt()
+        } else {
+            return Vec::new();
+        };
+        let v = if is_zero(v) {
+            0.
+        } else if v > 0. {
+            v.sqrt()
+        } else {
+            return Vec::new();
+        };
+        let v = if q < 0. { -v } else { v };
+
+        let mut roots = solve_quadratic(1., v, z - u);
+        roots.extend(solve_quadratic(1., -v, z + u));
+        roots
+    };
+
+    let sub = a_n / 4.;
+    let polynomial = |x: f64| x.mul_add(x.mul_add(x.mul_add(x.mul_add(a, b), c), d), e);
+    let derivative = |x: f64| x.mul_add(x.mul_add(x.mul_add(4. * a, 3. * b), 2. * c), d);
+    for root in &mut roots {
+        *root -= sub;
+        for _ in 0..2 {
+            let slope = derivative(*root);
+            if is_zero(slope) {
+                break;
+            }
+            *root -= polynomial(*root) / slope;
+        }
+    }
+    roots.sort_by(f64::total_cmp);
+    roots
+}
@@ -1,5 +1,6 @@
-use std::fs::OpenOptions;
+use std::fs::{self, OpenOptions};
 use std::io::{BufWriter, Write};
+use std::path::Path;
 
 use crate::color::Color;
 
@@ -43,6 +44,81 @@ impl PPMImage {
         Ok(&mut self.data[index])
     }
 
+    /// Reads PPM (P3, P6) and PGM (P2, P5) images.
+    ///
+    /// Grayscale images are loaded with the same value in all channels.
+    /// Values are rescaled to 0-255, if the file uses another max value.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let bytes = fs::read(path)?;
+        let mut reader = HeaderReader {
+            bytes: &bytes,
+            pos: 0,
+        };
+        let magic = reader.token()?;
+        let (channels, binary) = match magic {
+            "P2" => (1, false),
+            "P3" => (3, false),
+            "P5" => (1, true),
+            "P6" => (3, true),
+            _ => anyhow::bail!("Unsupported image format {magic}"),
+        };
+        let width: usize = reader.token()?.parse()?;
+        let height: usize = reader.token()?.parse()?;
+        let max_value: u32 = reader.token()?.parse()?;
+        if max_value == 0 || max_value > u32::from(u16::MAX) {
+            anyhow::bail!("Invalid max value {max_value}");
+        }
+
+        if width == 0 || height == 0 {
+            anyhow::bail!("Invalid image size {width}x{height}");
+        }
+
+        let Some(count) = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(channels))
+        else {
+            anyhow::bail!("Image size {width}x{height} is too large");
+        };
+        let samples: Vec<u32> = if binary {
+            // Exactly one whitespace separates header from data.
+            let data = bytes.get(reader.pos + 1..).unwrap_or_default();
+            if max_value < 256 {
+                data.iter().take(count).map(|&v| u32::from(v)).collect()
+            } else {
+                data.chunks_exact(2)
+                    .take(count)
+                    .map(|pair| u32::from(u16::from_be_bytes([pair[0], pair[1]])))
+                    .collect()
+            }
+        } else {
+            (0..count)
+                .map(|_| Ok(reader.token()?.parse()?))
+                .collect::<anyhow::Result<_>>()?
+        };
+        if samples.len() != count {
+            anyhow::bail!("Image data is truncated");
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let scale = |value: u32| (value.min(max_value) * 255 / max_value) as u8;
+        let data = samples
+            .chunks_exact(channels)
+            .map(|pixel| {
+                if let [r, g, b] = pixel {
+                    Color::new(scale(*r), scale(*g), scale(*b))
+                } else {
+                    let value = scale(pixel[0]);
+                    Color::new(value, value, value)
+                }
+            })
+            .collect();
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
     pub fn save(&self, filename: &str) -> anyhow::Result<()> {
         let file = OpenOptions::new()
             .write(true)
@@ -62,6 +138,39 @@ impl PPMImage {
     }
 }
 
+/// Splits header of netpbm files into tokens,
+/// skipping comments.
+struct HeaderReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> HeaderReader<'a> {
+    fn token(&mut self) -> anyhow::Result<&'a str> {
+        loop {
+            match self.bytes.get(self.pos) {
+                Some(b'#') => {
+                    while self.bytes.get(self.pos).is_some_and(|&c| c != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(_) => break,
+                None => anyhow::bail!("Unexpected end of file"),
+            }
+        }
+        let start = self.pos;
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|c| !c.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+        Ok(std::str::from_utf8(&self.bytes[start..self.pos])?)
+    }
+}
+
 /// Convert from list of list of colors to an image.
 ///
 /// First vec should represent y row,
@@ -0,0 +1,160 @@
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    aov::Aovs,
+    camera::{Camera, Tracer},
+    filter::SplatBuffer,
+    framebuffer::Framebuffer,
+    integrators::Integrator,
+    ppm::PPMImage,
+    renderables::Renderable,
+    vec3::Vec3,
+};
+
+/// Rows rendered between checks of the time budget.
+const BAND_HEIGHT: usize = 8;
+
+/// Render that gets better with every pass.
+///
+/// Each pass adds one sample to every pixel. Current average
+/// can be looked at any time, so the render can be stopped
+/// once it looks good enough.
+///
+/// A pass may be left unfinished when the time runs out.
+/// Rows it has reached already have one sample more than the rest.
+#[derive(Debug)]
+pub struct ProgressiveRender<'a, S> {
+    camera: &'a Camera,
+    scene: &'a S,
+    integrator: Box<dyn Integrator + 'a>,
+    /// Samples are splatted through the filter of the camera,
+    /// or just added to their pixels if there is none.
+    samples: SplatBuffer,
+    passes: usize,
+    /// First row that the current pass hasn't reached yet.
+    next_row: usize,
+    /// Rendered only once, when the camera has a denoiser.
+    aovs: Option<Aovs>,
+}
+
+impl<'a, S: Renderable + Sync> ProgressiveRender<'a, S> {
+    #[must_use]
+    pub fn new(camera: &'a Camera, scene: &'a S) -> Self {
+        Self {
+            camera,
+            scene,
+            integrator: Box::new(camera.path_tracer()),
+            samples: SplatBuffer::new(
+                camera.filter().unwrap_or_default(),
+                camera.output_width,
+                camera.output_height(),
+                0..camera.output_height(),
+            ),
+            passes: 0,
+            next_row: 0,
+            aovs: None,
+        }
+    }
+
+    /// Replaces the path tracer of the camera.
+    /// Should be set before the first pass.
+    #[must_use]
+    pub fn with_integrator(mut self, integrator: impl Integrator + 'a) -> Self {
+        self.integrator = Box::new(integrator);
+        self
+    }
+
+    /// Finishes the current pass, so every pixel
+    /// gets one sample more.
+    pub fn pass(&mut self) {
+        self.render_rows(self.next_row..self.camera.output_height());
+    }
+
+    /// Adds samples band by band until the time runs out.
+    ///
+    /// It stops early if the next band is expected to exceed
+    /// the budget, but always renders at least one band.
+    pub fn pass_for(&mut self, budget: Duration) {
+        let start = Instant::now();
+        let mut slowest_band = Duration::ZERO;
+        loop {
+            let band_start = Instant::now();
+            let end = (self.next_row + BAND_HEIGHT).min(self.camera.output_height());
+            self.render_rows(self.next_row..end);
+            slowest_band = slowest_band.max(band_start.elapsed());
+            if start.elapsed() + slowest_band > budget {
+                break;
+            }
+        }
+    }
+
+    /// Adds a sample to every pixel of the rows
+    /// and moves the current pass past them.
+    fn render_rows(&mut self, rows: Range<usize>) {
+        let camera = self.camera;
+        let tracer = Tracer {
+            scene: self.scene,
+            integrator: self.integrator.as_ref(),
+        };
+        let index = self.passes;
+        if let Some(filter) = camera.filter() {
+            let band = camera.splat_rows(rows.clone(), filter, |x, y, buffer| {
+                let (offset, color) = camera.sample_pixel(x, y, index, &tracer);
+                buffer.splat(x, y, offset, color);
+            });
+            self.samples.add(&band);
+        } else {
+            let width = camera.output_width;
+            let pixels = rows.start * width..rows.end * width;
+            let colors: Vec<Vec3> = pixels
+                .clone()
+                .into_par_iter()
+                .map(|pixel| {
+                    camera
+                        .sample_pixel(pixel % width, pixel / width, index, &tracer)
+                        .1
+                })
+                .collect();
+            for (pixel, color) in pixels.zip(colors) {
+                self.samples.accumulate(pixel % width, pixel / width, color);
+            }
+        }
+        self.next_row = rows.end;
+        if self.next_row >= camera.output_height() {
+            self.passes += 1;
+            self.next_row = 0;
+        }
+    }
+
+    /// Number of finished passes.
+    #[must_use]
+    pub const fn passes(&self) -> usize {
+        self.passes
+    }
+
+    /// Current average of all samples, without denoising.
+    #[must_use]
+    pub fn average(&self) -> Framebuffer {
+        self.samples.resolve()
+    }
+
+    /// Current average, denoised if the camera has a denoiser.
+    pub fn to_image(&mut self) -> PPMImage {
+        let average = self.average();
+        match self.camera.denoiser() {
+            Some(denoiser) => {
+                let aovs = self
+                    .aovs
+                    .get_or_insert_with(|| self.camera.get_aovs(self.scene));
+                denoiser.denoise(&average, aovs)
+            }
+            None => average,
+        }
+        .to_image()
+    }
+}
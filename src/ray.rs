@@ -4,12 +4,25 @@ use crate::vec3::{Point3, Vec3};
 pub struct Ray {
     pub origin: Point3,
     pub direction: Vec3,
+    /// Wavelength in nanometers. It's set only after the ray
+    /// hits a dispersive material and carries only one color.
+    pub wavelength: Option<f32>,
 }
 
 impl Ray {
     #[must_use]
     pub const fn new(origin: Point3, direction: Vec3) -> Self {
-        Self { origin, direction }
+        Self {
+            origin,
+            direction,
+            wavelength: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_wavelength(mut self, wavelength: Option<f32>) -> Self {
+        self.wavelength = wavelength;
+        self
     }
 
     #[must_use]
@@ -0,0 +1,60 @@
+use crate::{
+    interval::Interval,
+    ray::Ray,
+    vec3::{Point3, Vec3},
+};
+
+/// Axis aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Aabb {
+    /// Corners can be passed in any order.
+    #[must_use]
+    pub fn new(a: Point3, b: Point3) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    #[must_use]
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    /// Position of a point relative to the box,
+    /// where min corner is (0, 0, 0) and max is (1, 1, 1).
+    #[must_use]
+    pub fn to_local(&self, point: Point3) -> Vec3 {
+        (point - self.min) / self.size()
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point3) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /// Slab method.
+    ///
+    /// Box is an intersection of three slabs, the space between
+    /// two parallel planes. For each slab we find `t` at which the ray
+    /// enters and leaves it. The ray is inside of the box only when it's
+    /// inside of all slabs at once, so we take the latest enter
+    /// and the earliest exit.
+    ///
+    /// Returns distances where the ray enters and leaves the box,
+    /// clipped to the interval.
+    #[must_use]
+    pub fn hit_interval(&self, ray: &Ray, interval: Interval) -> Option<(f32, f32)> {
+        let inv_direction = ray.direction.recip();
+        let t0 = (self.min - ray.origin) * inv_direction;
+        let t1 = (self.max - ray.origin) * inv_direction;
+        let enter = t0.min(t1).max_element().max(interval.min);
+        let exit = t0.max(t1).min_element().min(interval.max);
+        (enter < exit).then_some((enter, exit))
+    }
+}
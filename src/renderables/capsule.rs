@@ -0,0 +1,85 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::Vec2;
+
+use crate::{
+    materials::Material,
+    renderables::{
+        HitRecord, RayData, Renderable, cylinder::tube_hits, instance::Transform, utils::SurfaceHit,
+    },
+    vec3::{Point3, Vec3},
+};
+
+/// All points within `radius` from the segment between `a` and `b`.
+#[derive(Debug, Clone)]
+pub struct Capsule {
+    pub radius: f32,
+    pub material: Arc<dyn Material>,
+    length: f32,
+    frame: Transform,
+}
+
+impl Capsule {
+    #[must_use]
+    pub fn new(a: Point3, b: Point3, radius: f32, material: Arc<dyn Material>) -> Self {
+        let axis = b - a;
+        Self {
+            radius,
+            material,
+            length: axis.length(),
+            frame: Transform::from_axis(a, axis),
+        }
+    }
+
+    /// Hits with a hemisphere at height `y`.
+    /// Only the half facing away from the body counts.
+    fn cap_hits(&self, ray: &RayData, y: f32, outward: f32) -> Vec<SurfaceHit> {
+        let center = Vec3::new(0., y, 0.);
+        let oc = center - ray.ray.origin;
+        let a = ray.ray.direction.length_squared();
+        let h = ray.ray.direction.dot(oc);
+        let c = self.radius.mul_add(-self.radius, oc.length_squared());
+        let discriminant = h.mul_add(h, -(a * c));
+        if discriminant < 0. {
+            return Vec::new();
+        }
+        let dsqrt = discriminant.sqrt();
+        [(h - dsqrt) / a, (h + dsqrt) / a]
+            .into_iter()
+            .filter_map(|t| {
+                let point = ray.ray.at(t);
+                if (point.y - y) * outward < 0. {
+                    return None;
+                }
+                let normal = (point - center) / self.radius;
+                Some(SurfaceHit::new(t, normal, self.uv(point)))
+            })
+            .collect()
+    }
+
+    /// U goes around the axis, V goes from one end to another.
+    fn uv(&self, point: Point3) -> Vec2 {
+        let angle = point.z.atan2(point.x) + PI;
+        let v = (point.y + self.radius) / 2.0f32.mul_add(self.radius, self.length);
+        Vec2::new(angle / (2. * PI), v)
+    }
+}
+
+/// Capsule is a cylinder with two hemispheres on its ends.
+/// We intersect all three and take the closest one.
+impl Renderable for Capsule {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let local = self.frame.ray_to_local(ray);
+        let mut hits = tube_hits(&local, self.radius, 0., self.length);
+        for hit in &mut hits {
+            hit.uv = self.uv(local.ray.at(hit.t));
+        }
+        hits.extend(self.cap_hits(&local, 0., -1.));
+        hits.extend(self.cap_hits(&local, self.length, 1.));
+        let hit = SurfaceHit::closest(local.interval, hits)?;
+        Some(
+            self.frame
+                .hit_to_world(hit.into_record(&local.ray, self.material.clone())),
+        )
+    }
+}
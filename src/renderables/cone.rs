@@ -0,0 +1,110 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::{Vec2, Vec3Swizzles};
+
+use crate::{
+    materials::Material,
+    renderables::{
+        HitRecord, RayData, Renderable, disk::cap_hit, instance::Transform, utils::SurfaceHit,
+    },
+    vec3::{Point3, Vec3},
+};
+
+/// Cone with a round base at `base` and apex
+/// at `height` along the `axis`.
+#[derive(Debug, Clone)]
+pub struct Cone {
+    pub radius: f32,
+    pub height: f32,
+    pub capped: bool,
+    pub material: Arc<dyn Material>,
+    frame: Transform,
+}
+
+impl Cone {
+    #[must_use]
+    pub fn new(
+        base: Point3,
+        axis: Vec3,
+        radius: f32,
+        height: f32,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            radius,
+            height,
+            capped: true,
+            material,
+            frame: Transform::from_axis(base, axis),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_caps(mut self, capped: bool) -> Self {
+        self.capped = capped;
+        self
+    }
+}
+
+/// Radius of the cone shrinks linearly with height.
+/// With k = r / h the surface is:
+///
+/// x^2 + z^2 = k^2 * (h - y)^2
+///
+/// Substituting the ray gives quadratic equation for t.
+impl Renderable for Cone {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let local = self.frame.ray_to_local(ray);
+        let origin = local.ray.origin;
+        let direction = local.ray.direction;
+        let k = self.radius / self.height;
+        let k2 = k * k;
+        let dy = self.height - origin.y;
+
+        let a = (k2 * direction.y).mul_add(-direction.y, direction.xz().length_squared());
+        let b = 2. * (k2 * dy).mul_add(direction.y, origin.xz().dot(direction.xz()));
+        let c = (k2 * dy).mul_add(-dy, origin.xz().length_squared());
+
+        let roots = if a.abs() < f32::EPSILON {
+            // Ray is parallel to the side of the cone.
+            if b.abs() < f32::EPSILON {
+                vec![]
+            } else {
+                vec![-c / b]
+            }
+        } else {
+            let discriminant = b.mul_add(b, -4. * a * c);
+            if discriminant < 0. {
+                vec![]
+            } else {
+                let dsqrt = discriminant.sqrt();
+                vec![(-b - dsqrt) / (2. * a), (-b + dsqrt) / (2. * a)]
+            }
+        };
+
+        let side = roots.into_iter().filter_map(|t| {
+            let point = local.ray.at(t);
+            if point.y < 0. || point.y > self.height {
+                return None;
+            }
+            // Gradient of the implicit surface.
+            let normal = Vec3::new(point.x, k2 * (self.height - point.y), point.z).normalize();
+            let angle = point.z.atan2(point.x) + PI;
+            Some(SurfaceHit::new(
+                t,
+                normal,
+                Vec2::new(angle / (2. * PI), point.y / self.height),
+            ))
+        });
+        let cap = self
+            .capped
+            .then(|| cap_hit(&local, 0., self.radius, -Vec3::Y))
+            .flatten();
+
+        let hit = SurfaceHit::closest(local.interval, side.chain(cap))?;
+        Some(
+            self.frame
+                .hit_to_world(hit.into_record(&local.ray, self.material.clone())),
+        )
+    }
+}
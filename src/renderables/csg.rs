@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use crate::{
+    ray::Ray,
+    renderables::{HitRecord, RayData, Renderable, Solid, Span},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    /// Inside of any operand.
+    Union,
+    /// Inside of both operands.
+    Intersection,
+    /// Inside of the left operand, but not the right one.
+    Difference,
+}
+
+impl CsgOperation {
+    const fn is_inside(self, left: bool, right: bool) -> bool {
+        match self {
+            Self::Union => left || right,
+            Self::Intersection => left && right,
+            Self::Difference => left && !right,
+        }
+    }
+}
+
+/// Constructive solid geometry.
+///
+/// Combines two solids into a new one, e.g. a sphere
+/// with a hole or a lens made of two spheres.
+/// Each surface keeps the material of the operand it came from.
+#[derive(Debug, Clone)]
+pub struct Csg {
+    pub operation: CsgOperation,
+    pub left: Arc<dyn Solid + Send + Sync>,
+    pub right: Arc<dyn Solid + Send + Sync>,
+}
+
+impl Csg {
+    #[must_use]
+    pub const fn new(
+        operation: CsgOperation,
+        left: Arc<dyn Solid + Send + Sync>,
+        right: Arc<dyn Solid + Send + Sync>,
+    ) -> Self {
+        Self {
+            operation,
+            left,
+            right,
+        }
+    }
+
+    #[must_use]
+    pub const fn union(
+        left: Arc<dyn Solid + Send + Sync>,
+        right: Arc<dyn Solid + Send + Sync>,
+    ) -> Self {
+        Self::new(CsgOperation::Union, left, right)
+    }
+
+    #[must_use]
+    pub const fn intersection(
+        left: Arc<dyn Solid + Send + Sync>,
+        right: Arc<dyn Solid + Send + Sync>,
+    ) -> Self {
+        Self::new(CsgOperation::Intersection, left, right)
+    }
+
+    #[must_use]
+    pub const fn difference(
+        left: Arc<dyn Solid + Send + Sync>,
+        right: Arc<dyn Solid + Send + Sync>,
+    ) -> Self {
+        Self::new(CsgOperation::Difference, left, right)
+    }
+}
+
+/// Boundary of an operand along the ray.
+struct Event {
+    hit: HitRecord,
+    is_left: bool,
+    is_enter: bool,
+}
+
+fn events(spans: Vec<Span>, is_left: bool) -> impl Iterator<Item = Event> {
+    spans.into_iter().flat_map(move |span| {
+        [
+            Event {
+                hit: span.enter,
+                is_left,
+                is_enter: true,
+            },
+            Event {
+                hit: span.exit,
+                is_left,
+                is_enter: false,
+            },
+        ]
+    })
+}
+
+/// We walk along the ray through boundaries of both operands,
+/// tracking whether we're inside of each of them. Every time
+/// the result of the operation changes, we're crossing the
+/// boundary of the combined solid.
+///
+/// Normals of hit records always face the ray, so to turn an exit
+/// of an operand into an enter of the result (or the other way around)
+/// we only need to fix `front_face`.
+impl Solid for Csg {
+    fn spans(&self, ray: &Ray) -> Vec<Span> {
+        let mut events: Vec<Event> = events(self.left.spans(ray), true)
+            .chain(events(self.right.spans(ray), false))
+            .collect();
+        events.sort_by(|a, b| a.hit.distance.total_cmp(&b.hit.distance));
+
+        let mut inside_left = false;
+        let mut inside_right = false;
+        let mut enter: Option<HitRecord> = None;
+        let mut spans = Vec::new();
+        for event in events {
+            let was_inside = self.operation.is_inside(inside_left, inside_right);
+            if event.is_left {
+                inside_left = event.is_enter;
+            } else {
+                inside_right = event.is_enter;
+            }
+            let is_inside = self.operation.is_inside(inside_left, inside_right);
+            let mut hit = event.hit;
+            hit.front_face = is_inside;
+            match (was_inside, is_inside) {
+                (false, true) => enter = Some(hit),
+                (true, false) => {
+                    if let Some(enter) = enter.take() {
+                        spans.push(Span::new(enter, hit));
+                    }
+                }
+                _ => {}
+            }
+        }
+        spans
+    }
+}
+
+impl Renderable for Csg {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        self.spans(&ray.ray)
+            .into_iter()
+            .flat_map(|span| [span.enter, span.exit])
+            .find(|hit| ray.interval.contains(hit.distance))
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use glam::Vec2;
+
+use crate::{
+    materials::Material,
+    ray::Ray,
+    renderables::{Aabb, HitRecord, RayData, Renderable, Solid, Span},
+    vec3::{Point3, Vec3},
+};
+
+/// Axis aligned box.
+#[derive(Debug, Clone)]
+pub struct Cuboid {
+    pub bounds: Aabb,
+    pub material: Arc<dyn Material>,
+}
+
+impl Cuboid {
+    /// Corners can be passed in any order.
+    #[must_use]
+    pub fn new(a: Point3, b: Point3, material: Arc<dyn Material>) -> Self {
+        Self {
+            bounds: Aabb::new(a, b),
+            material,
+        }
+    }
+
+    /// Both points where the ray crosses the box surface.
+    /// Distances aren't limited by any interval.
+    fn hit_both(&self, ray: &Ray) -> Option<(HitRecord, HitRecord)> {
+        let inv_direction = ray.direction.recip();
+        let t0 = (self.bounds.min - ray.origin) * inv_direction;
+        let t1 = (self.bounds.max - ray.origin) * inv_direction;
+        let near = t0.min(t1);
+        let far = t0.max(t1);
+        let enter = near.max_element();
+        let exit = far.min_element();
+        if enter.is_nan() || exit.is_nan() || enter > exit {
+            return None;
+        }
+        // Face is the one where the last slab was entered
+        // or the first one was left.
+        let enter_axis = near.max_position();
+        let exit_axis = far.min_position();
+        let sign = ray.direction.signum();
+        let enter_normal = -axis_vector(enter_axis) * sign[enter_axis];
+        let exit_normal = axis_vector(exit_axis) * sign[exit_axis];
+        Some((
+            self.record(ray, enter, enter_normal, enter_axis),
+            self.record(ray, exit, exit_normal, exit_axis),
+        ))
+    }
+
+    fn record(&self, ray: &Ray, t: f32, normal: Vec3, axis: usize) -> HitRecord {
+        let point = ray.at(t);
+        let local = self.bounds.to_local(point);
+        // Each face gets the whole texture using
+        // two other axes as coordinates.
+        let (uv, tangent) = match axis {
+            0 => (Vec2::new(local.z, local.y), Vec3::Z),
+            1 => (Vec2::new(local.x, local.z), Vec3::X),
+            _ => (Vec2::new(local.x, local.y), Vec3::X),
+        };
+        HitRecord::new_with_ray(ray, &point, &normal, t, self.material.clone())
+            .with_uv(uv)
+            .with_tangent(tangent)
+    }
+}
+
+const fn axis_vector(axis: usize) -> Vec3 {
+    match axis {
+        0 => Vec3::X,
+        1 => Vec3::Y,
+        _ => Vec3::Z,
+    }
+}
+
+/// Uses slab method, see `Aabb::hit_interval`.
+impl Renderable for Cuboid {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let (enter, exit) = self.hit_both(&ray.ray)?;
+        if ray.interval.contains(enter.distance) {
+            Some(enter)
+        } else if ray.interval.contains(exit.distance) {
+            // Ray starts inside of the box.
+            Some(exit)
+        } else {
+            None
+        }
+    }
+}
+
+impl Solid for Cuboid {
+    fn spans(&self, ray: &Ray) -> Vec<Span> {
+        self.hit_both(ray)
+            .map(|(enter, exit)| Span::new(enter, exit))
+            .into_iter()
+            .collect()
+    }
+}
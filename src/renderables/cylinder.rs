@@ -0,0 +1,124 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::{Vec2, Vec3Swizzles};
+
+use crate::{
+    interval::Interval,
+    materials::Material,
+    ray::Ray,
+    renderables::{
+        HitRecord, RayData, Renderable, Solid, Span, disk::cap_hit, instance::Transform,
+        utils::SurfaceHit,
+    },
+    vec3::{Point3, Vec3},
+};
+
+/// Cylinder that starts at `base` and goes along `axis` for `height`.
+///
+/// It's capped by default, uncapped one is just a tube.
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    pub radius: f32,
+    pub height: f32,
+    pub capped: bool,
+    pub material: Arc<dyn Material>,
+    frame: Transform,
+}
+
+impl Cylinder {
+    #[must_use]
+    pub fn new(
+        base: Point3,
+        axis: Vec3,
+        radius: f32,
+        height: f32,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            radius,
+            height,
+            capped: true,
+            material,
+            frame: Transform::from_axis(base, axis),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_caps(mut self, capped: bool) -> Self {
+        self.capped = capped;
+        self
+    }
+
+    /// All surface hits in local space, unsorted.
+    fn local_hits(&self, ray: &RayData) -> Vec<SurfaceHit> {
+        let mut hits = tube_hits(ray, self.radius, 0., self.height);
+        if self.capped {
+            hits.extend(cap_hit(ray, 0., self.radius, -Vec3::Y));
+            hits.extend(cap_hit(ray, self.height, self.radius, Vec3::Y));
+        }
+        hits
+    }
+}
+
+/// Side of an infinite cylinder around `Y` axis, clipped by height.
+///
+/// Same as sphere, but we ignore Y coordinate:
+/// x^2 + z^2 = r^2
+pub(super) fn tube_hits(ray: &RayData, radius: f32, min_y: f32, max_y: f32) -> Vec<SurfaceHit> {
+    let origin = ray.ray.origin.xz();
+    let direction = ray.ray.direction.xz();
+    let a = direction.length_squared();
+    let h = -origin.dot(direction);
+    let c = radius.mul_add(-radius, origin.length_squared());
+    let discriminant = h.mul_add(h, -(a * c));
+    if a < f32::EPSILON || discriminant < 0. {
+        return Vec::new();
+    }
+    let dsqrt = discriminant.sqrt();
+    [(h - dsqrt) / a, (h + dsqrt) / a]
+        .into_iter()
+        .filter_map(|t| {
+            let point = ray.ray.at(t);
+            if point.y < min_y || point.y > max_y {
+                return None;
+            }
+            let normal = Vec3::new(point.x, 0., point.z) / radius;
+            let angle = point.z.atan2(point.x) + PI;
+            let v = (point.y - min_y) / (max_y - min_y);
+            Some(SurfaceHit::new(t, normal, Vec2::new(angle / (2. * PI), v)))
+        })
+        .collect()
+}
+
+impl Renderable for Cylinder {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let local = self.frame.ray_to_local(ray);
+        let hit = SurfaceHit::closest(local.interval, self.local_hits(&local))?;
+        Some(
+            self.frame
+                .hit_to_world(hit.into_record(&local.ray, self.material.clone())),
+        )
+    }
+}
+
+/// Uncapped cylinder isn't closed,
+/// so it gives spans only for rays that go through the tube.
+impl Solid for Cylinder {
+    fn spans(&self, ray: &Ray) -> Vec<Span> {
+        let local = self.frame.ray_to_local(&RayData {
+            ray: *ray,
+            interval: Interval::UNIVERSE,
+        });
+        let mut hits = self.local_hits(&local);
+        hits.sort_by(|a, b| a.t.total_cmp(&b.t));
+        // Ray through the edge of a cap hits both the cap and the side.
+        hits.dedup_by(|b, a| (b.t - a.t).abs() <= 1e-4 * a.t.abs().max(1.));
+        let record = |hit: SurfaceHit| {
+            self.frame
+                .hit_to_world(hit.into_record(&local.ray, self.material.clone()))
+        };
+        hits.chunks_exact(2)
+            .map(|pair| Span::new(record(pair[0]), record(pair[1])))
+            .collect()
+    }
+}
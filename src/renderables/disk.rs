@@ -0,0 +1,59 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::{Vec2, Vec3Swizzles};
+
+use crate::{
+    materials::Material,
+    renderables::{HitRecord, RayData, Renderable, instance::Transform, utils::SurfaceHit},
+    vec3::{Point3, Vec3},
+};
+
+/// Flat round disk.
+#[derive(Debug, Clone)]
+pub struct Disk {
+    pub radius: f32,
+    pub material: Arc<dyn Material>,
+    frame: Transform,
+}
+
+impl Disk {
+    #[must_use]
+    pub fn new(center: Point3, normal: Vec3, radius: f32, material: Arc<dyn Material>) -> Self {
+        Self {
+            radius,
+            material,
+            frame: Transform::from_axis(center, normal),
+        }
+    }
+}
+
+/// Polar coordinates on a disk of given radius in XZ plane.
+pub(super) fn disk_uv(point: Point3, radius: f32) -> Vec2 {
+    let angle = point.z.atan2(point.x) + PI;
+    Vec2::new(angle / (2. * PI), point.xz().length() / radius)
+}
+
+/// Hit with a cap at height `y` of shapes defined around `Y` axis.
+pub(super) fn cap_hit(ray: &RayData, y: f32, radius: f32, normal: Vec3) -> Option<SurfaceHit> {
+    let t = (y - ray.ray.origin.y) / ray.ray.direction.y;
+    if !t.is_finite() {
+        return None;
+    }
+    let point = ray.ray.at(t);
+    (point.xz().length_squared() <= radius * radius)
+        .then(|| SurfaceHit::new(t, normal, disk_uv(point, radius)))
+}
+
+/// In its own space disk lies in XZ plane with center at (0, 0, 0).
+/// So it's just a plane with a limit on distance from the center.
+impl Renderable for Disk {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let local = self.frame.ray_to_local(ray);
+        let hit = cap_hit(&local, 0., self.radius, Vec3::Y)
+            .filter(|hit| local.interval.contains(hit.t))?;
+        Some(
+            self.frame
+                .hit_to_world(hit.into_record(&local.ray, self.material.clone())),
+        )
+    }
+}
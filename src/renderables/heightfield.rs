@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use glam::{Vec2, Vec3Swizzles};
+
+use crate::{
+    materials::Material,
+    ppm::PPMImage,
+    renderables::{Aabb, HitRecord, RayData, Renderable, triangle::moller_trumbore},
+    vec3::{Point3, Vec3},
+};
+
+/// Terrain defined by a grid of heights.
+///
+/// The grid is stretched over the bounding box: X and Z of samples
+/// go along the box, heights in range [0, 1] go from its bottom
+/// to the top. Each grid cell is rendered as two triangles
+/// with normals interpolated between vertices.
+#[derive(Debug, Clone)]
+pub struct HeightField {
+    pub bounds: Aabb,
+    pub material: Arc<dyn Material>,
+    width: usize,
+    depth: usize,
+    heights: Vec<f32>,
+    normals: Vec<Vec3>,
+}
+
+impl HeightField {
+    /// Heights are in row-major order, `width` samples along X axis
+    /// for each of `depth` rows along Z axis.
+    pub fn new(
+        bounds: Aabb,
+        width: usize,
+        depth: usize,
+        heights: Vec<f32>,
+        material: Arc<dyn Material>,
+    ) -> anyhow::Result<Self> {
+        if width < 2 || depth < 2 {
+            anyhow::bail!("Height field needs at least 2x2 samples");
+        }
+        if heights.len() != width * depth {
+            anyhow::bail!(
+                "Height field {width}x{depth} requires {} samples, got {}",
+                width * depth,
+                heights.len()
+            );
+        }
+        let mut field = Self {
+            bounds,
+            material,
+            width,
+            depth,
+            heights,
+            normals: Vec::new(),
+        };
+        field.normals = (0..depth)
+            .flat_map(|z| (0..width).map(move |x| (x, z)))
+            .map(|(x, z)| field.vertex_normal(x, z))
+            .collect();
+        Ok(field)
+    }
+
+    /// Brightness of the image is used as height.
+    pub fn from_image(
+        bounds: Aabb,
+        image: &PPMImage,
+        material: Arc<dyn Material>,
+    ) -> anyhow::Result<Self> {
+        let heights = image
+            .data
+            .iter()
+            .map(|&color| Vec3::from(color).dot(Vec3::splat(1. / 3.)))
+            .collect();
+        Self::new(bounds, image.width, image.height, heights, material)
+    }
+
+    /// Samples function at every grid point. Function gets position
+    /// in range [0, 1] and should return height in the same range.
+    pub fn from_fn(
+        bounds: Aabb,
+        width: usize,
+        depth: usize,
+        func: impl Fn(Vec2) -> f32,
+        material: Arc<dyn Material>,
+    ) -> anyhow::Result<Self> {
+        let heights = (0..depth)
+            .flat_map(|z| (0..width).map(move |x| (x, z)))
+            .map(|(x, z)| {
+                func(Vec2::new(
+                    x as f32 / (width - 1) as f32,
+                    z as f32 / (depth - 1) as f32,
+                ))
+            })
+            .collect();
+        Self::new(bounds, width, depth, heights, material)
+    }
+
+    fn cell_size(&self) -> Vec2 {
+        self.bounds.size().xz() / Vec2::new((self.width - 1) as f32, (self.depth - 1) as f32)
+    }
+
+    fn vertex(&self, x: usize, z: usize) -> Point3 {
+        let cell = self.cell_size();
+        let size = self.bounds.size();
+        self.bounds.min
+            + Vec3::new(
+                x as f32 * cell.x,
+                self.heights[z * self.width + x] * size.y,
+                z as f32 * cell.y,
+            )
+    }
+
+    /// Central differences of neighbouring heights.
+    fn vertex_normal(&self, x: usize, z: usize) -> Vec3 {
+        let left = self.vertex(x.saturating_sub(1), z);
+        let right = self.vertex((x + 1).min(self.width - 1), z);
+        let back = self.vertex(x, z.saturating_sub(1));
+        let front = self.vertex(x, (z + 1).min(self.depth - 1));
+        (front - back).cross(right - left).normalize_or(Vec3::Y)
+    }
+
+    /// Two triangles of the cell. Returns the closest hit
+    /// as distance and interpolated normal.
+    #[allow(clippy::many_single_char_names)]
+    fn hit_cell(&self, ray: &RayData, x: usize, z: usize) -> Option<(f32, Vec3)> {
+        let corners = [(x, z), (x + 1, z), (x + 1, z + 1), (x, z + 1)];
+        let [a, b, c, d] = corners.map(|(x, z)| self.vertex(x, z));
+        let [na, nb, nc, nd] = corners.map(|(x, z)| self.normals[z * self.width + x]);
+        [(a, b, c, na, nb, nc), (a, c, d, na, nc, nd)]
+            .into_iter()
+            .filter_map(|(a, b, c, na, nb, nc)| {
+                let (t, u, v) = moller_trumbore(&ray.ray, a, b, c)?;
+                let normal = (na * (1. - u - v) + nb * u + nc * v).normalize();
+                ray.interval.contains(t).then_some((t, normal))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+    }
+}
+
+/// Grid traversal.
+///
+/// Looking from above, the ray crosses cells of the grid one after another.
+/// We walk through them in order with 2D DDA, same as drawing a line
+/// on a pixel grid. The first cell with a hit has the closest one,
+/// so there's no need to check the rest of the terrain.
+impl Renderable for HeightField {
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let (start, end) = self.bounds.hit_interval(&ray.ray, ray.interval)?;
+        let cell = self.cell_size();
+        let origin = (ray.ray.at(start).xz() - self.bounds.min.xz()) / cell;
+        let direction = ray.ray.direction.xz() / cell;
+        let max_cell = Vec2::new((self.width - 2) as f32, (self.depth - 2) as f32);
+        let mut current = origin.floor().clamp(Vec2::ZERO, max_cell);
+
+        // Distance along the ray to cross one cell on each axis,
+        // and to the next cell border.
+        let step = direction.signum();
+        let delta = direction.recip().abs();
+        let next_border = current + step.max(Vec2::ZERO);
+        let mut next_t = ((next_border - origin) / direction).abs();
+        if direction.x == 0. {
+            next_t.x = f32::INFINITY;
+        }
+        if direction.y == 0. {
+            next_t.y = f32::INFINITY;
+        }
+
+        loop {
+            let (x, z) = (current.x as usize, current.y as usize);
+            if let Some((t, normal)) = self.hit_cell(ray, x, z) {
+                let point = ray.ray.at(t);
+                let uv = self.bounds.to_local(point).xz();
+                return Some(
+                    HitRecord::new_with_ray(&ray.ray, &point, &normal, t, self.material.clone())
+                        .with_uv(uv)
+                        .with_tangent(Vec3::X),
+                );
+            }
+            let step_t = next_t.min_element();
+            if start + step_t > end {
+                return None;
+            }
+            if next_t.x < next_t.y {
+                current.x += step.x;
+                next_t.x += delta.x;
+            } else {
+                current.y += step.y;
+                next_t.y += delta.y;
+            }
+            if current.cmplt(Vec2::ZERO).any() || current.cmpgt(max_cell).any() {
+                return None;
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use glam::{Affine3A, Mat3A, Quat};
+
+use crate::{
+    ray::Ray,
+    interval::Interval,
+    renderables::{HitRecord, RayData, Renderable, Solid, Span},
+    vec3::{Point3, Vec3},
+};
+
+/// Affine transform with everything we need
+/// to move rays and hits between spaces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Transform {
+    pub matrix: Affine3A,
+    inverse: Affine3A,
+    /// Normals don't transform as points do.
+    /// To keep them perpendicular to the surface under
+    /// non-uniform scale we need inverse transpose of the linear part.
+    normal_matrix: Mat3A,
+}
+
+impl Transform {
+    pub fn new(transform: Affine3A) -> Self {
+        let inverse = transform.inverse();
+        Self {
+            matrix: transform,
+            inverse,
+            normal_matrix: inverse.matrix3.transpose(),
+        }
+    }
+
+    /// Rigid transform that moves `Y` axis to the given one.
+    ///
+    /// Analytic shapes are defined around `Y` axis
+    /// and use it to be placed in the scene.
+    /// Zero axis leaves the shape upright.
+    pub fn from_axis(origin: Point3, axis: Vec3) -> Self {
+        Self::new(Affine3A::from_rotation_translation(
+            Quat::from_rotation_arc(Vec3::Y, axis.try_normalize().unwrap_or(Vec3::Y)),
+            origin,
+        ))
+    }
+
+    /// Direction isn't normalized, so `t` in object space
+    /// is the same as in world space.
+    pub fn ray_to_local(&self, ray: &RayData) -> RayData {
+        RayData {
+            ray: Ray::new(
+                self.inverse.transform_point3(ray.ray.origin),
+                self.inverse.transform_vector3(ray.ray.direction),
+            )
+            .with_wavelength(ray.ray.wavelength),
+            interval: ray.interval,
+        }
+    }
+
+    pub fn hit_to_world(&self, mut hit: HitRecord) -> HitRecord {
+        hit.point = self.matrix.transform_point3(hit.point);
+        hit.normal = self.normal_matrix.mul_vec3(hit.normal).normalize();
+        hit.tangent = self.matrix.transform_vector3(hit.tangent);
+        hit
+    }
+
+    pub fn hit(&self, object: &(impl Renderable + ?Sized), ray: &RayData) -> Option<HitRecord> {
+        object
+            .hit(&self.ray_to_local(ray))
+            .map(|hit| self.hit_to_world(hit))
+    }
+}
+
+/// Places any renderable in the scene with translation,
+/// rotation and scale.
+///
+/// Object itself is shared, so one heavy mesh can be
+/// instanced many times without copying it.
+/// Instances of solids are solids too, so they can be used in CSG.
+#[derive(Debug)]
+pub struct Instance<T: ?Sized = dyn Renderable + Send + Sync> {
+    pub object: Arc<T>,
+    transform: Transform,
+}
+
+impl<T: ?Sized> Clone for Instance<T> {
+    fn clone(&self) -> Self {
+        Self {
+            object: self.object.clone(),
+            transform: self.transform,
+        }
+    }
+}
+
+impl<T: ?Sized> Instance<T> {
+    #[must_use]
+    pub fn new(object: Arc<T>, transform: Affine3A) -> Self {
+        Self {
+            object,
+            transform: Transform::new(transform),
+        }
+    }
+
+    #[must_use]
+    pub const fn transform(&self) -> Affine3A {
+        self.transform.matrix
+    }
+
+    pub fn set_transform(&mut self, transform: Affine3A) {
+        self.transform = Transform::new(transform);
+    }
+}
+
+impl<T: Renderable + ?Sized> Renderable for Instance<T> {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let mut hit = self.transform.hit(self.object.as_ref(), ray)?;
+        // Each instance of a shared object gets its own ID.
+        hit.set_object(self);
+        Some(hit)
+    }
+
+    fn transmittance(&self, ray: &RayData) -> f32 {
+        self.object.transmittance(&self.transform.ray_to_local(ray))
+    }
+}
+
+impl<T: Solid + ?Sized> Solid for Instance<T> {
+    fn spans(&self, ray: &Ray) -> Vec<Span> {
+        let local = self.transform.ray_to_local(&RayData {
+            ray: *ray,
+            interval: Interval::UNIVERSE,
+        });
+        self.object
+            .spans(&local.ray)
+            .into_iter()
+            .map(|span| {
+                Span::new(
+                    self.transform.hit_to_world(span.enter),
+                    self.transform.hit_to_world(span.exit),
+                )
+            })
+            .collect()
+    }
+}
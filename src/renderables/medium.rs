@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::{
+    interval::Interval,
+    materials::Material,
+    renderables::{HitRecord, RayData, Renderable},
+};
+
+/// Volume of constant density, like smoke or mist.
+///
+/// Boundary must be a closed shape, so that
+/// every ray that enters it also leaves it.
+/// Material should be a phase function, such as `Isotropic`.
+#[derive(Debug, Clone)]
+pub struct ConstantMedium {
+    pub boundary: Arc<dyn Renderable + Send + Sync>,
+    pub density: f32,
+    pub phase: Arc<dyn Material>,
+}
+
+impl ConstantMedium {
+    #[must_use]
+    pub const fn new(
+        boundary: Arc<dyn Renderable + Send + Sync>,
+        density: f32,
+        phase: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            boundary,
+            density,
+            phase,
+        }
+    }
+
+    /// Part of the ray's interval that lies inside of the boundary.
+    fn segment(&self, ray: &RayData) -> Option<(f32, f32)> {
+        // Ray might start inside of the volume, so we look
+        // for both boundaries along the whole line.
+        let enter = self.boundary.hit(&RayData {
+            ray: ray.ray,
+            interval: Interval::UNIVERSE,
+        })?;
+        let exit = self.boundary.hit(&RayData {
+            ray: ray.ray,
+            interval: Interval::new(enter.distance + 0.0001, f32::INFINITY),
+        })?;
+
+        let start = enter.distance.max(ray.interval.min).max(0.);
+        let end = exit.distance.min(ray.interval.max);
+        (start < end).then_some((start, end))
+    }
+}
+
+/// Light that travels `distance` through a medium
+/// of given density without scattering.
+fn beer_lambert(density: f32, distance: f32) -> f32 {
+    (-density * distance).exp()
+}
+
+/// Samples a point where the ray scatters inside
+/// of a medium between `start` and `end`.
+///
+/// Probability to travel distance `d` without scattering is exp(-density * d).
+/// By inverting it, we get a random distance at which scattering happens.
+pub(super) fn sample_scattering(
+    ray: &RayData,
+    start: f32,
+    end: f32,
+    density: f32,
+    phase: &Arc<dyn Material>,
+) -> Option<HitRecord> {
+    let ray_length = ray.ray.direction.length();
+    let distance_inside = (end - start) * ray_length;
+    let u: f32 = rand::rng().random_range(0.0..1.0);
+    let hit_distance = -(1. - u).ln() / density;
+    if hit_distance > distance_inside {
+        return None;
+    }
+    let t = start + hit_distance / ray_length;
+    let point = ray.ray.at(t);
+    // Normal is meaningless for volumes,
+    // so we just face it against the ray.
+    let normal = -ray.ray.direction / ray_length;
+    Some(HitRecord::new_with_ray(
+        &ray.ray,
+        &point,
+        &normal,
+        t,
+        phase.clone(),
+    ))
+}
+
+impl Renderable for ConstantMedium {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let (start, end) = self.segment(ray)?;
+        sample_scattering(ray, start, end, self.density, &self.phase)
+    }
+
+    fn transmittance(&self, ray: &RayData) -> f32 {
+        self.segment(ray).map_or(1., |(start, end)| {
+            beer_lambert(self.density, (end - start) * ray.ray.direction.length())
+        })
+    }
+}
+
+/// Global fog, that fills the space between camera and objects.
+///
+/// Rays that don't hit anything aren't affected by fog,
+/// unless the fog has finite `max_distance`. Otherwise
+/// the whole sky would be hidden behind it.
+#[derive(Debug, Clone)]
+pub struct Fog {
+    pub density: f32,
+    pub max_distance: f32,
+    pub phase: Arc<dyn Material>,
+}
+
+impl Fog {
+    #[must_use]
+    pub const fn new(density: f32, phase: Arc<dyn Material>) -> Self {
+        Self {
+            density,
+            max_distance: f32::INFINITY,
+            phase,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// End of the fog along the ray, if the ray has to pass it.
+    fn end(&self, ray: &RayData, closest: f32) -> Option<f32> {
+        // Distances along the ray are in units of its direction.
+        let end = closest.min(self.max_distance / ray.ray.direction.length());
+        (end.is_finite() && end > ray.interval.min).then_some(end)
+    }
+
+    #[must_use]
+    pub fn hit(&self, ray: &RayData, closest: f32) -> Option<HitRecord> {
+        let end = self.end(ray, closest)?;
+        sample_scattering(ray, ray.interval.min, end, self.density, &self.phase)
+    }
+
+    /// Fraction of light that gets through the fog before `closest`.
+    #[must_use]
+    pub fn transmittance(&self, ray: &RayData, closest: f32) -> f32 {
+        self.end(ray, closest).map_or(1., |end| {
+            let distance = (end - ray.interval.min) * ray.ray.direction.length();
+            beer_lambert(self.density, distance)
+        })
+    }
+}
@@ -1,13 +1,46 @@
+mod aabb;
+mod capsule;
+mod cone;
+mod cuboid;
+mod csg;
+mod cylinder;
+mod disk;
+mod heightfield;
+mod instance;
+mod medium;
+mod node;
 mod plane;
+mod quad;
 mod scene;
+mod sdf;
 mod sphere;
+mod torus;
 mod traits;
 mod utils;
 mod triangle;
+mod volume;
 
-pub use plane::Plane;
+pub use aabb::Aabb;
+pub use capsule::Capsule;
+pub use cone::Cone;
+pub use cuboid::Cuboid;
+pub use csg::{Csg, CsgOperation};
+pub use cylinder::Cylinder;
+pub use disk::Disk;
+pub use heightfield::HeightField;
+pub use instance::Instance;
+pub use medium::{ConstantMedium, Fog};
+pub use node::Node;
+pub use plane::{Plane, PlaneExtent};
+pub use quad::Quad;
 pub use scene::Scene;
+pub use sdf::{
+    DistanceFunction, FnDistance, Sdf, SdfBox, SdfRoundBox, SdfSphere, SdfTorus, SmoothSubtraction,
+    SmoothUnion,
+};
 pub use sphere::Sphere;
-pub use traits::Renderable;
-pub use utils::{HitRecord, RayData};
+pub use torus::Torus;
+pub use traits::{Renderable, Solid};
+pub use utils::{HitRecord, RayData, Span};
 pub use triangle::Triangle;
+pub use volume::{DensityGrid, HeterogeneousMedium, RawFormat};
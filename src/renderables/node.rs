@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use glam::Affine3A;
+
+use crate::{
+    interval::Interval,
+    renderables::{HitRecord, RayData, Renderable, instance::Transform},
+};
+
+/// Named node of a scene graph.
+///
+/// Each node may hold an object and any number of children.
+/// Transform is relative to the parent, so moving a node
+/// moves all of its children as well. Nodes without objects
+/// are just groups.
+///
+/// Nodes are addressed by paths of names separated with `/`,
+/// like `car/wheels/front_left`.
+#[derive(Debug, Clone)]
+pub struct Node {
+    name: String,
+    transform: Transform,
+    pub object: Option<Arc<dyn Renderable + Send + Sync>>,
+    pub children: Vec<Self>,
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl Node {
+    /// Creates an empty group.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            transform: Transform::new(Affine3A::IDENTITY),
+            object: None,
+            children: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_object(mut self, object: Arc<dyn Renderable + Send + Sync>) -> Self {
+        self.object = Some(object);
+        self
+    }
+
+    #[must_use]
+    pub fn with_transform(mut self, transform: Affine3A) -> Self {
+        self.set_transform(transform);
+        self
+    }
+
+    #[must_use]
+    pub fn with_child(mut self, child: Self) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub const fn transform(&self) -> Affine3A {
+        self.transform.matrix
+    }
+
+    pub fn set_transform(&mut self, transform: Affine3A) {
+        self.transform = Transform::new(transform);
+    }
+
+    pub fn add_child(&mut self, child: Self) {
+        self.children.push(child);
+    }
+
+    #[must_use]
+    pub fn child(&self, name: &str) -> Option<&Self> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn child_mut(&mut self, name: &str) -> Option<&mut Self> {
+        self.children.iter_mut().find(|child| child.name == name)
+    }
+
+    /// Looks for a descendant by path relative to this node.
+    #[must_use]
+    pub fn find(&self, path: &str) -> Option<&Self> {
+        split_path(path).try_fold(self, |node, name| node.child(name))
+    }
+
+    pub fn find_mut(&mut self, path: &str) -> Option<&mut Self> {
+        split_path(path).try_fold(self, |node, name| node.child_mut(name))
+    }
+
+    /// Detaches a descendant with all its children.
+    pub fn remove(&mut self, path: &str) -> Option<Self> {
+        let (parent, name) = match path.trim_matches('/').rsplit_once('/') {
+            Some((parent, name)) => (self.find_mut(parent)?, name),
+            None => (self, path.trim_matches('/')),
+        };
+        let index = parent
+            .children
+            .iter()
+            .position(|child| child.name == name)?;
+        Some(parent.children.remove(index))
+    }
+
+    /// Hit in the parent's space, without the node's own transform.
+    fn hit_local(&self, ray: &RayData) -> Option<HitRecord> {
+        let mut res = self.object.as_ref().and_then(|object| {
+            let mut hit = object.hit(ray)?;
+            // The object may be shared by several nodes,
+            // so the node itself identifies the placement.
+            hit.set_object(self);
+            Some(hit)
+        });
+        let mut closest = res.as_ref().map_or(ray.interval.max, |hit| hit.distance);
+        for object in &self.children {
+            if let Some(hit) = object.hit(&RayData {
+                ray: ray.ray,
+                interval: Interval::new(ray.interval.min, closest),
+            }) {
+                closest = hit.distance;
+                res = Some(hit);
+            }
+        }
+        res
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|name| !name.is_empty())
+}
+
+impl Renderable for Node {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let local = self.transform.ray_to_local(ray);
+        self.hit_local(&local)
+            .map(|hit| self.transform.hit_to_world(hit))
+    }
+
+    fn transmittance(&self, ray: &RayData) -> f32 {
+        let local = self.transform.ray_to_local(ray);
+        let own = self
+            .object
+            .as_ref()
+            .map_or(1., |object| object.transmittance(&local));
+        own * self
+            .children
+            .iter()
+            .map(|child| child.transmittance(&local))
+            .product::<f32>()
+    }
+}
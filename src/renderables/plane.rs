@@ -1,16 +1,40 @@
 use std::sync::Arc;
 
+use glam::Vec2;
+
 use crate::{
     materials::Material,
     renderables::Renderable,
     vec3::{Point3, Vec3},
 };
 
+/// Limits of a plane around its origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaneExtent {
+    Infinite,
+    /// Half sizes along the plane's tangent and bitangent.
+    Rectangle {
+        half_width: f32,
+        half_height: f32,
+    },
+    Disk {
+        radius: f32,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct Plane {
     pub origin: Point3,
     pub normal: Vec3,
     pub material: Arc<dyn Material>,
+    pub extent: PlaneExtent,
+    /// One-sided planes are invisible from behind.
+    pub one_sided: bool,
+    /// Size of one texture tile in world units.
+    pub uv_scale: Vec2,
+    /// Direction of U axis of texture coordinates.
+    /// If not set, any direction along the plane is used.
+    pub tangent: Option<Vec3>,
 }
 
 impl Plane {
@@ -20,8 +44,48 @@ impl Plane {
             origin,
             normal,
             material,
+            extent: PlaneExtent::Infinite,
+            one_sided: false,
+            uv_scale: Vec2::ONE,
+            tangent: None,
         }
     }
+
+    #[must_use]
+    pub const fn with_extent(mut self, extent: PlaneExtent) -> Self {
+        self.extent = extent;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_one_sided(mut self, one_sided: bool) -> Self {
+        self.one_sided = one_sided;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_uv_scale(mut self, uv_scale: Vec2) -> Self {
+        self.uv_scale = uv_scale;
+        self
+    }
+
+    /// Tangent is projected on the plane, so it doesn't
+    /// need to be exactly perpendicular to the normal.
+    #[must_use]
+    pub fn with_tangent(mut self, tangent: Vec3) -> Self {
+        let normal = self.normal.normalize();
+        self.tangent = (tangent - normal * tangent.dot(normal)).try_normalize();
+        self
+    }
+
+    /// Tangent and bitangent of the plane.
+    fn basis(&self) -> (Vec3, Vec3) {
+        let normal = self.normal.normalize();
+        self.tangent.map_or_else(
+            || normal.any_orthonormal_pair(),
+            |tangent| (tangent, normal.cross(tangent)),
+        )
+    }
 }
 
 /// Plane is simple to render.
@@ -53,23 +117,48 @@ impl Plane {
 /// -> Q · N + ( D * t ) · N - `P_0` · N = 0
 /// -> t = ((`P_0` - Q) · N) / (d · N)
 ///
-/// In case if t is less than zero, it means that
-/// there's no intersection.
+/// If t is outside of the ray's interval, there's no intersection.
 ///
+/// For bounded planes we additionally check coordinates
+/// of the point along the plane's basis.
 impl Renderable for Plane {
     fn hit(&self, ray: &super::RayData) -> Option<super::HitRecord> {
-        let t =
-            (self.origin - ray.ray.origin).dot(self.normal) / ray.ray.direction.dot(self.normal);
-        if t < 0. || !ray.interval.contains(t) {
+        let denominator = ray.ray.direction.dot(self.normal);
+        // Rays parallel to the plane never hit it.
+        if denominator.abs() < 1e-8 || (self.one_sided && denominator > 0.) {
+            return None;
+        }
+        let t = (self.origin - ray.ray.origin).dot(self.normal) / denominator;
+        if !ray.interval.contains(t) {
             return None;
         }
         let point = ray.ray.at(t);
-        Some(super::HitRecord::new_with_ray(
-            &ray.ray,
-            &point,
-            &self.normal,
-            t,
-            self.material.clone(),
-        ))
+        // UV coordinates are just coordinates of a point
+        // in plane's own basis.
+        let (u_axis, v_axis) = self.basis();
+        let local = point - self.origin;
+        let planar = Vec2::new(local.dot(u_axis), local.dot(v_axis));
+        let inside = match self.extent {
+            PlaneExtent::Infinite => true,
+            PlaneExtent::Rectangle {
+                half_width,
+                half_height,
+            } => planar.x.abs() <= half_width && planar.y.abs() <= half_height,
+            PlaneExtent::Disk { radius } => planar.length_squared() <= radius * radius,
+        };
+        if !inside {
+            return None;
+        }
+        Some(
+            super::HitRecord::new_with_ray(
+                &ray.ray,
+                &point,
+                &self.normal,
+                t,
+                self.material.clone(),
+            )
+            .with_uv(planar / self.uv_scale)
+            .with_tangent(u_axis),
+        )
     }
 }
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use glam::Vec2;
+
+use crate::{
+    materials::Material,
+    renderables::{HitRecord, RayData, Renderable},
+    vec3::{Point3, Vec3},
+};
+
+/// Parallelogram, defined by a corner and two edges.
+///
+/// Normal follows right-hand rule: it's `u × v`.
+#[derive(Debug, Clone)]
+pub struct Quad {
+    pub origin: Point3,
+    pub u: Vec3,
+    pub v: Vec3,
+    pub material: Arc<dyn Material>,
+    normal: Vec3,
+    /// Helper vector to get plane coordinates
+    /// of a point: `n / (n · n)` where `n = u × v`.
+    w: Vec3,
+}
+
+impl Quad {
+    #[must_use]
+    pub fn new(origin: Point3, u: Vec3, v: Vec3, material: Arc<dyn Material>) -> Self {
+        let n = u.cross(v);
+        Self {
+            origin,
+            u,
+            v,
+            material,
+            normal: n.normalize(),
+            w: n / n.length_squared(),
+        }
+    }
+
+    #[must_use]
+    pub const fn normal(&self) -> Vec3 {
+        self.normal
+    }
+}
+
+/// First we intersect the plane of the quad, same as `Plane` does.
+///
+/// Then we need to know whether the point is inside of the parallelogram.
+/// Any point on the plane can be written as
+///
+/// P = Q + α * u + β * v
+///
+/// And the point is inside only when both α and β are in [0, 1].
+/// Crossing both sides with u or v and dotting with w removes one of the
+/// terms, so we get
+///
+/// α = w · (p × v)
+/// β = w · (u × p)
+///
+/// where p = P - Q. As a bonus α and β are texture coordinates.
+impl Renderable for Quad {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let denominator = self.normal.dot(ray.ray.direction);
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+        let t = (self.origin - ray.ray.origin).dot(self.normal) / denominator;
+        if !ray.interval.contains(t) {
+            return None;
+        }
+        let point = ray.ray.at(t);
+        let planar = point - self.origin;
+        let alpha = self.w.dot(planar.cross(self.v));
+        let beta = self.w.dot(self.u.cross(planar));
+        if !(0. ..=1.).contains(&alpha) || !(0. ..=1.).contains(&beta) {
+            return None;
+        }
+        Some(
+            HitRecord::new_with_ray(&ray.ray, &point, &self.normal, t, self.material.clone())
+                .with_uv(Vec2::new(alpha, beta))
+                .with_tangent(self.u),
+        )
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     interval::Interval,
-    renderables::{HitRecord, RayData, Renderable},
+    renderables::{Fog, HitRecord, Node, RayData, Renderable},
 };
 
 type RenderableObject = dyn Renderable + Sync;
@@ -8,6 +8,8 @@ type RenderableObject = dyn Renderable + Sync;
 #[derive(Default, Debug)]
 pub struct Scene {
     objects: Vec<Box<RenderableObject>>,
+    fog: Option<Fog>,
+    root: Node,
 }
 
 impl Scene {
@@ -19,6 +21,28 @@ impl Scene {
         self.objects.extend(objects);
     }
 
+    /// Adds a node to the top level of the scene graph.
+    pub fn add_node(&mut self, node: Node) {
+        self.root.add_child(node);
+    }
+
+    #[must_use]
+    pub fn node(&self, path: &str) -> Option<&Node> {
+        self.root.find(path)
+    }
+
+    pub fn node_mut(&mut self, path: &str) -> Option<&mut Node> {
+        self.root.find_mut(path)
+    }
+
+    pub fn remove_node(&mut self, path: &str) -> Option<Node> {
+        self.root.remove(path)
+    }
+
+    pub fn set_fog(&mut self, fog: Option<Fog>) {
+        self.fog = fog;
+    }
+
     pub fn get_mut(&mut self, index: usize) -> Option<&mut Box<RenderableObject>> {
         self.objects.get_mut(index)
     }
@@ -27,7 +51,10 @@ impl Scene {
 impl Renderable for Scene {
     fn hit(&self, ray: &RayData) -> Option<HitRecord> {
         let mut closest = ray.interval.max;
-        let mut res = None;
+        let mut res = self.root.hit(ray);
+        if let Some(hit) = &res {
+            closest = hit.distance;
+        }
         for obj in &self.objects {
             let tmp_res = obj.hit(&RayData {
                 ray: ray.ray,
@@ -35,17 +62,40 @@ impl Renderable for Scene {
                 // hits further than the closest found so far
                 interval: Interval::new(ray.interval.min, closest),
             });
-            if let Some(hit) = tmp_res {
+            if let Some(mut hit) = tmp_res {
+                hit.set_object(obj.as_ref());
                 closest = hit.distance;
                 res = Some(hit);
             }
         }
+        if let Some(fog) = &self.fog
+            && let Some(fog_hit) = fog.hit(ray, closest)
+        {
+            return Some(fog_hit);
+        }
         res
     }
+
+    fn transmittance(&self, ray: &RayData) -> f32 {
+        let fog = self
+            .fog
+            .as_ref()
+            .map_or(1., |fog| fog.transmittance(ray, ray.interval.max));
+        self.objects
+            .iter()
+            .map(|obj| obj.transmittance(ray))
+            .product::<f32>()
+            * self.root.transmittance(ray)
+            * fog
+    }
 }
 
 impl Renderable for &Scene {
     fn hit(&self, ray: &RayData) -> Option<HitRecord> {
         (*self).hit(ray)
     }
+
+    fn transmittance(&self, ray: &RayData) -> f32 {
+        (*self).transmittance(ray)
+    }
 }
@@ -0,0 +1,251 @@
+use std::{fmt::Debug, sync::Arc};
+
+use glam::{Vec2, Vec3Swizzles};
+
+use crate::{
+    materials::Material,
+    renderables::{HitRecord, RayData, Renderable},
+    vec3::{Point3, Vec3},
+};
+
+/// Signed distance to a surface.
+///
+/// Negative inside, positive outside. The value must never
+/// be bigger than the real distance, otherwise sphere tracing
+/// jumps through the surface.
+pub trait DistanceFunction: Debug + Send + Sync {
+    fn distance(&self, point: Point3) -> f32;
+}
+
+/// Wrapper to use any function or closure as a distance function.
+pub struct FnDistance<F>(pub F);
+
+impl<F> Debug for FnDistance<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FnDistance")
+    }
+}
+
+impl<F: Fn(Point3) -> f32 + Send + Sync> DistanceFunction for FnDistance<F> {
+    fn distance(&self, point: Point3) -> f32 {
+        (self.0)(point)
+    }
+}
+
+/// Sphere around (0, 0, 0).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfSphere {
+    pub radius: f32,
+}
+
+impl DistanceFunction for SdfSphere {
+    fn distance(&self, point: Point3) -> f32 {
+        point.length() - self.radius
+    }
+}
+
+/// Box around (0, 0, 0) with given half sizes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfBox {
+    pub half_size: Vec3,
+}
+
+impl DistanceFunction for SdfBox {
+    fn distance(&self, point: Point3) -> f32 {
+        let q = point.abs() - self.half_size;
+        q.max(Vec3::ZERO).length() + q.max_element().min(0.)
+    }
+}
+
+/// Box with edges rounded by `radius`.
+/// Radius is subtracted from the box, so the outer size stays the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfRoundBox {
+    pub half_size: Vec3,
+    pub radius: f32,
+}
+
+impl DistanceFunction for SdfRoundBox {
+    fn distance(&self, point: Point3) -> f32 {
+        let inner = SdfBox {
+            half_size: self.half_size - self.radius,
+        };
+        inner.distance(point) - self.radius
+    }
+}
+
+/// Torus around `Y` axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfTorus {
+    pub major_radius: f32,
+    pub minor_radius: f32,
+}
+
+impl DistanceFunction for SdfTorus {
+    fn distance(&self, point: Point3) -> f32 {
+        let q = Vec2::new(point.xz().length() - self.major_radius, point.y);
+        q.length() - self.minor_radius
+    }
+}
+
+/// Union of two shapes blended together.
+/// `k` defines the size of the blending area.
+#[derive(Debug, Clone)]
+pub struct SmoothUnion {
+    pub a: Arc<dyn DistanceFunction>,
+    pub b: Arc<dyn DistanceFunction>,
+    pub k: f32,
+}
+
+impl DistanceFunction for SmoothUnion {
+    fn distance(&self, point: Point3) -> f32 {
+        let d1 = self.a.distance(point);
+        let d2 = self.b.distance(point);
+        let h = (0.5 + 0.5 * (d2 - d1) / self.k).clamp(0., 1.);
+        (self.k * h).mul_add(h - 1., (d2 - d1).mul_add(1. - h, d1))
+    }
+}
+
+/// Shape `a` with `b` carved out of it.
+/// `k` defines how smooth the carved edges are.
+#[derive(Debug, Clone)]
+pub struct SmoothSubtraction {
+    pub a: Arc<dyn DistanceFunction>,
+    pub b: Arc<dyn DistanceFunction>,
+    pub k: f32,
+}
+
+impl DistanceFunction for SmoothSubtraction {
+    fn distance(&self, point: Point3) -> f32 {
+        let d1 = self.a.distance(point);
+        let d2 = self.b.distance(point);
+        let h = (0.5 - 0.5 * (d1 + d2) / self.k).clamp(0., 1.);
+        (self.k * h).mul_add(1. - h, (-d2 - d1).mul_add(h, d1))
+    }
+}
+
+/// Renders any distance function by sphere tracing.
+///
+/// Shape is defined in its own space around (0, 0, 0),
+/// use `Instance` to place it in the scene.
+#[derive(Debug, Clone)]
+pub struct Sdf {
+    pub function: Arc<dyn DistanceFunction>,
+    pub material: Arc<dyn Material>,
+    pub max_steps: usize,
+    pub max_distance: f32,
+    pub epsilon: f32,
+    /// Multiplier for each step. Values below one
+    /// make tracing safe for inexact distance functions.
+    pub step_scale: f32,
+}
+
+impl Sdf {
+    #[must_use]
+    pub const fn new(function: Arc<dyn DistanceFunction>, material: Arc<dyn Material>) -> Self {
+        Self {
+            function,
+            material,
+            max_steps: 256,
+            max_distance: 100.,
+            epsilon: 1e-4,
+            step_scale: 1.,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_step_scale(mut self, step_scale: f32) -> Self {
+        self.step_scale = step_scale;
+        self
+    }
+
+    /// Gradient of the distance function is the normal.
+    /// It's estimated with four samples around the point
+    /// placed in vertices of a tetrahedron.
+    fn normal(&self, point: Point3) -> Vec3 {
+        let h = self.epsilon;
+        [
+            Vec3::new(1., -1., -1.),
+            Vec3::new(-1., -1., 1.),
+            Vec3::new(-1., 1., -1.),
+            Vec3::ONE,
+        ]
+        .into_iter()
+        .map(|k| k * self.function.distance(point + k * h))
+        .sum::<Vec3>()
+        .normalize_or(Vec3::Y)
+    }
+}
+
+/// Sphere tracing.
+///
+/// Distance function tells us how far the closest surface is,
+/// so we can safely move along the ray by this distance.
+/// We repeat it until we get close enough to the surface.
+///
+/// Rays that start inside of the shape (e.g. refracted ones)
+/// march with inverted distance, so they stop at the exit.
+///
+/// Rays that start on the surface (e.g. scattered ones) first
+/// have to leave it, otherwise they would hit their own origin.
+/// Only then we know whether they go inside or outside.
+impl Renderable for Sdf {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let length = ray.ray.direction.length();
+        let mut t = ray.interval.min.max(0.);
+        let mut sign = None;
+        let max_t = ray.interval.max.min(self.max_distance / length);
+        for _ in 0..self.max_steps {
+            let point = ray.ray.at(t);
+            let distance = self.function.distance(point);
+            let sign = match sign {
+                Some(sign) => sign,
+                None if distance.abs() < self.epsilon => {
+                    t += self.epsilon / length;
+                    if t > max_t {
+                        return None;
+                    }
+                    continue;
+                }
+                None => *sign.insert(distance.signum()),
+            };
+            let distance = distance * sign;
+            if distance < self.epsilon {
+                if !ray.interval.contains(t) {
+                    return None;
+                }
+                let normal = self.normal(point);
+                return Some(HitRecord::new_with_ray(
+                    &ray.ray,
+                    &point,
+                    &normal,
+                    t,
+                    self.material.clone(),
+                ));
+            }
+            t += distance * self.step_scale / length;
+            if t > max_t {
+                return None;
+            }
+        }
+        None
+    }
+}
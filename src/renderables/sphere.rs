@@ -1,9 +1,12 @@
-use std::sync::Arc;
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::Vec2;
 
 use crate::{
     materials::Material,
-    renderables::{HitRecord, Renderable},
-    vec3::Point3,
+    ray::Ray,
+    renderables::{HitRecord, Renderable, Solid, Span},
+    vec3::{Point3, Vec3},
 };
 
 #[derive(Debug, Clone)]
@@ -23,6 +26,23 @@ impl Sphere {
         }
     }
 }
+
+/// Maps a point on a unit sphere to texture coordinates.
+///
+/// u goes around Y axis starting from -X,
+/// v goes from the bottom pole to the top one.
+fn sphere_uv(normal: Vec3) -> Vec2 {
+    let theta = (-normal.y).clamp(-1., 1.).acos();
+    let phi = (-normal.z).atan2(normal.x) + PI;
+    Vec2::new(phi / (2. * PI), theta / PI)
+}
+
+/// Derivative of a point on the sphere by u coordinate.
+/// It's zero at the poles.
+const fn sphere_tangent(normal: Vec3) -> Vec3 {
+    Vec3::new(normal.z, 0., -normal.x)
+}
+
 /// Sphere has this formula
 ///
 /// x^2 + y^2 + z^2 = r^2
@@ -127,13 +147,33 @@ impl Renderable for Sphere {
         }
         let point = ray.ray.at(root);
         let normal = (point - self.origin) / self.radius;
-        Some(HitRecord::new_with_ray(
-            &ray.ray,
-            &point,
-            &normal,
-            root,
-            self.material.clone(),
-        ))
+        Some(
+            HitRecord::new_with_ray(&ray.ray, &point, &normal, root, self.material.clone())
+                .with_uv(sphere_uv(normal))
+                .with_tangent(sphere_tangent(normal)),
+        )
+    }
+}
+
+impl Solid for Sphere {
+    fn spans(&self, ray: &Ray) -> Vec<Span> {
+        let oc = self.origin - ray.origin;
+        let a = ray.direction.length_squared();
+        let h = ray.direction.dot(oc);
+        let c = self.radius.mul_add(-self.radius, oc.length_squared());
+        let discriminant = h.mul_add(h, -(a * c));
+        if discriminant <= 0. {
+            return Vec::new();
+        }
+        let dsqrt = discriminant.sqrt();
+        let record = |t: f32| {
+            let point = ray.at(t);
+            let normal = (point - self.origin) / self.radius;
+            HitRecord::new_with_ray(ray, &point, &normal, t, self.material.clone())
+                .with_uv(sphere_uv(normal))
+                .with_tangent(sphere_tangent(normal))
+        };
+        vec![Span::new(record((h - dsqrt) / a), record((h + dsqrt) / a))]
     }
 }
 
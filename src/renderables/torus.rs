@@ -0,0 +1,100 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use glam::{DVec3, Vec2, Vec3Swizzles};
+
+use crate::{
+    materials::Material,
+    polynomial::solve_quartic,
+    renderables::{HitRecord, RayData, Renderable, instance::Transform, utils::SurfaceHit},
+    vec3::{Point3, Vec3},
+};
+
+/// Donut around the `axis`.
+///
+/// Major radius is the distance from the center to the
+/// middle of the tube, minor radius is the radius of the tube.
+#[derive(Debug, Clone)]
+pub struct Torus {
+    pub major_radius: f32,
+    pub minor_radius: f32,
+    pub material: Arc<dyn Material>,
+    frame: Transform,
+}
+
+impl Torus {
+    #[must_use]
+    pub fn new(
+        center: Point3,
+        axis: Vec3,
+        major_radius: f32,
+        minor_radius: f32,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            major_radius,
+            minor_radius,
+            material,
+            frame: Transform::from_axis(center, axis),
+        }
+    }
+}
+
+/// Torus around `Y` axis is described by
+///
+/// (x^2 + y^2 + z^2 + R^2 - r^2)^2 = 4R^2 * (x^2 + z^2)
+///
+/// Which can be rewritten as
+///
+/// (|P|^2 - R^2 - r^2)^2 = 4R^2 * (r^2 - y^2)
+///
+/// After substitution of P = Q + D * t we get quartic equation.
+/// With e = |Q|^2 - R^2 - r^2 and f = Q · D:
+///
+/// (D · D)^2 * t^4
+/// + 4 * (D · D) * f * t^3
+/// + (2 * (D · D) * e + 4f^2 + 4R^2 * `D_y^2`) * t^2
+/// + (4 * f * e + 8R^2 * `Q_y` * `D_y`) * t
+/// + e^2 - 4R^2 * (r^2 - `Q_y^2`) = 0
+///
+/// To keep it stable we solve it for normalized direction.
+impl Renderable for Torus {
+    #[allow(clippy::many_single_char_names)]
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let local = self.frame.ray_to_local(ray);
+        let length = local.ray.direction.length();
+        let origin = local.ray.origin.as_dvec3();
+        let direction = (local.ray.direction / length).as_dvec3();
+        let major2 = f64::from(self.major_radius).powi(2);
+        let minor2 = f64::from(self.minor_radius).powi(2);
+
+        let e = origin.length_squared() - major2 - minor2;
+        let f = origin.dot(direction);
+        let four_major2 = 4. * major2;
+        let roots = solve_quartic(
+            1.,
+            4. * f,
+            (four_major2 * direction.y).mul_add(direction.y, 2.0f64.mul_add(e, 4. * f * f)),
+            (four_major2 * 2. * origin.y).mul_add(direction.y, 4. * f * e),
+            four_major2.mul_add(origin.y.mul_add(origin.y, -minor2), e * e),
+        );
+
+        #[allow(clippy::cast_possible_truncation)]
+        let hits = roots.into_iter().map(|t| {
+            let t = t as f32 / length;
+            let point = local.ray.at(t);
+            // Normal points from the closest point
+            // on the circle inside of the tube.
+            let ring = DVec3::new(f64::from(point.x), 0., f64::from(point.z));
+            let center = ring.normalize_or_zero() * major2.sqrt();
+            let normal = (point.as_dvec3() - center).normalize().as_vec3();
+            let u = point.z.atan2(point.x) + PI;
+            let v = point.y.atan2(point.xz().length() - self.major_radius) + PI;
+            SurfaceHit::new(t, normal, Vec2::new(u / (2. * PI), v / (2. * PI)))
+        });
+        let hit = SurfaceHit::closest(local.interval, hits)?;
+        Some(
+            self.frame
+                .hit_to_world(hit.into_record(&local.ray, self.material.clone())),
+        )
+    }
+}
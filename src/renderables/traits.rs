@@ -1,3 +1,32 @@
+use crate::ray::Ray;
+
 pub trait Renderable: std::fmt::Debug {
     fn hit(&self, ray: &super::RayData) -> Option<super::HitRecord>;
+
+    /// Fraction of light that gets through the object
+    /// along the ray, within its interval.
+    ///
+    /// Each surface on the way lets through what its opacity leaves.
+    /// Volumes estimate it instead, and groups ask their members.
+    fn transmittance(&self, ray: &super::RayData) -> f32 {
+        let mut ray = *ray;
+        let mut transmittance = 1.;
+        while transmittance > 0.
+            && let Some(hit) = self.hit(&ray)
+        {
+            transmittance *= (1. - hit.material_ref.opacity(&hit)).max(0.);
+            ray.interval.min = hit.distance + 0.001;
+        }
+        transmittance
+    }
+}
+
+/// Closed shape that knows its inside.
+///
+/// Instead of the closest hit it returns all segments of the ray
+/// that lie inside of the shape, which is required for CSG.
+pub trait Solid: Renderable {
+    /// Spans along the whole line of the ray, sorted by distance.
+    /// They aren't limited by any interval, so they might be behind the ray.
+    fn spans(&self, ray: &Ray) -> Vec<super::Span>;
 }
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
+use glam::Vec2;
+
 use crate::{
     materials::Material,
+    ray::Ray,
     renderables::{HitRecord, Renderable},
     vec3::Point3,
 };
@@ -38,48 +41,58 @@ impl Triangle {
 /// But it's the fastest way to calculate it.
 ///
 /// Google for Möller-Trumbore algorithm
-impl Renderable for Triangle {
-    #[allow(clippy::many_single_char_names)]
-    fn hit(&self, ray: &super::RayData) -> Option<super::HitRecord> {
-        let edge1 = self.b - self.a;
-        let edge2 = self.c - self.a;
-        let h = ray.ray.direction.cross(edge2);
-        let det = edge1.dot(h);
+///
+/// Returns distance and barycentric coordinates of the hit.
+#[allow(clippy::many_single_char_names)]
+pub(super) fn moller_trumbore(
+    ray: &Ray,
+    a: Point3,
+    b: Point3,
+    c: Point3,
+) -> Option<(f32, f32, f32)> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let h = ray.direction.cross(edge2);
+    let det = edge1.dot(h);
 
-        if det.abs() < f32::EPSILON {
-            // The ray is parallel to triangle.
-            return None;
-        }
+    if det.abs() < f32::EPSILON {
+        // The ray is parallel to triangle.
+        return None;
+    }
 
-        let inv_det = 1.0 / det;
-        let s = ray.ray.origin - self.a;
-        let u = inv_det * s.dot(h);
+    let inv_det = 1.0 / det;
+    let s = ray.origin - a;
+    let u = inv_det * s.dot(h);
 
-        if (u < 0.0 && u.abs() > f32::EPSILON) || (u > 1.0 && (u - 1.).abs() > f32::EPSILON) {
-            return None;
-        }
+    if (u < 0.0 && u.abs() > f32::EPSILON) || (u > 1.0 && (u - 1.).abs() > f32::EPSILON) {
+        return None;
+    }
 
-        let q = s.cross(edge1);
-        let v = inv_det * ray.ray.direction.dot(q);
-        if (v < 0.0 && v.abs() > f32::EPSILON) || (u + v > 1.0 && (u + v - 1.).abs() > f32::EPSILON)
-        {
-            return None;
-        }
+    let q = s.cross(edge1);
+    let v = inv_det * ray.direction.dot(q);
+    if (v < 0.0 && v.abs() > f32::EPSILON) || (u + v > 1.0 && (u + v - 1.).abs() > f32::EPSILON) {
+        return None;
+    }
+
+    Some((inv_det * edge2.dot(q), u, v))
+}
 
-        let t = inv_det * edge2.dot(q);
+impl Renderable for Triangle {
+    fn hit(&self, ray: &super::RayData) -> Option<super::HitRecord> {
+        let (t, u, v) = moller_trumbore(&ray.ray, self.a, self.b, self.c)?;
 
         if !ray.interval.contains(t) || t <= f32::EPSILON {
             return None;
         }
 
+        let edge1 = self.b - self.a;
+        let edge2 = self.c - self.a;
         let point = ray.ray.at(t);
-        let normal = (edge1.cross(edge2) - self.a).normalize();
-        Some(HitRecord::new_with_ray(
-            &ray.ray,
-            &point,
-            &normal,
-            t,
-            self.material.clone(),
-        ))
+        let normal = edge1.cross(edge2).normalize();
+        Some(
+            HitRecord::new_with_ray(&ray.ray, &point, &normal, t, self.material.clone())
+                .with_uv(Vec2::new(u, v))
+                .with_tangent(edge1),
+        )
     }
 }
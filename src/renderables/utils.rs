@@ -1,5 +1,7 @@
 use std::{fmt::Debug, sync::Arc};
 
+use glam::Vec2;
+
 use crate::{
     interval::Interval,
     materials::Material,
@@ -13,6 +15,13 @@ pub struct HitRecord {
     pub normal: Vec3,
     pub distance: f32,
     pub front_face: bool,
+    pub uv: Vec2,
+    /// Direction in which U texture coordinate grows.
+    /// Zero if the surface has no such direction.
+    pub tangent: Vec3,
+    /// Identifies the scene object that was hit.
+    /// Zero if the object is unknown.
+    pub object_id: usize,
     pub material_ref: Arc<dyn Material>,
 }
 
@@ -30,6 +39,9 @@ impl HitRecord {
             normal: *normal,
             distance,
             front_face: false,
+            uv: Vec2::ZERO,
+            tangent: Vec3::ZERO,
+            object_id: 0,
             material_ref,
         };
         record.front_face = ray.direction.dot(*normal) < 0.;
@@ -38,6 +50,59 @@ impl HitRecord {
         }
         record
     }
+
+    #[must_use]
+    pub const fn with_uv(mut self, uv: Vec2) -> Self {
+        self.uv = uv;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_tangent(mut self, tangent: Vec3) -> Self {
+        self.tangent = tangent;
+        self
+    }
+
+    /// Marks the hit as belonging to the given object.
+    ///
+    /// ID is the address of the object. If an inner object already
+    /// set one, both are combined, so a shared object gets a different
+    /// ID in every place it's put in.
+    pub fn set_object<T: ?Sized>(&mut self, object: *const T) {
+        let outer = object.cast::<()>().addr();
+        self.object_id = if self.object_id == 0 {
+            outer
+        } else {
+            (outer ^ self.object_id.rotate_left(17)).wrapping_mul(0x9E37_79B9) | 1
+        };
+    }
+
+    /// Orthonormal tangent, bitangent and normal.
+    ///
+    /// Tangent is made perpendicular to the normal. If the surface
+    /// has no tangent, any perpendicular direction is used.
+    #[must_use]
+    pub fn tangent_frame(&self) -> (Vec3, Vec3, Vec3) {
+        let normal = self.normal;
+        let tangent = (self.tangent - normal * self.tangent.dot(normal))
+            .try_normalize()
+            .unwrap_or_else(|| normal.any_orthonormal_pair().0);
+        (tangent, normal.cross(tangent), normal)
+    }
+}
+
+/// Part of a ray inside of a solid.
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub enter: HitRecord,
+    pub exit: HitRecord,
+}
+
+impl Span {
+    #[must_use]
+    pub const fn new(enter: HitRecord, exit: HitRecord) -> Self {
+        Self { enter, exit }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -45,3 +110,35 @@ pub struct RayData {
     pub ray: Ray,
     pub interval: Interval,
 }
+
+/// Point on a surface of an analytic shape,
+/// before it's turned into a `HitRecord`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct SurfaceHit {
+    pub t: f32,
+    /// Outward normal.
+    pub normal: Vec3,
+    pub uv: Vec2,
+}
+
+impl SurfaceHit {
+    pub const fn new(t: f32, normal: Vec3, uv: Vec2) -> Self {
+        Self { t, normal, uv }
+    }
+
+    /// The closest hit that fits into the interval.
+    pub fn closest(interval: Interval, hits: impl IntoIterator<Item = Self>) -> Option<Self> {
+        hits.into_iter()
+            .filter(|hit| interval.contains(hit.t))
+            .min_by(|a, b| a.t.total_cmp(&b.t))
+    }
+
+    /// All shapes with surface hits use angle around `Y` axis
+    /// as U coordinate, so the tangent goes around the axis too.
+    pub fn into_record(self, ray: &Ray, material: Arc<dyn Material>) -> HitRecord {
+        let point = ray.at(self.t);
+        HitRecord::new_with_ray(ray, &point, &self.normal, self.t, material)
+            .with_uv(self.uv)
+            .with_tangent(Vec3::new(-point.z, 0., point.x))
+    }
+}
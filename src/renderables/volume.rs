@@ -0,0 +1,247 @@
+use std::{fs, path::Path, sync::Arc};
+
+use rand::Rng;
+
+use crate::{
+    interval::Interval,
+    materials::Material,
+    ray::Ray,
+    renderables::{Aabb, HitRecord, RayData, Renderable},
+    vec3::Vec3,
+};
+
+/// Storage format of raw voxel files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawFormat {
+    /// One byte per voxel, mapped to [0, 1].
+    U8,
+    /// Little endian 32-bit floats.
+    F32,
+}
+
+/// Scalar values on a regular 3D grid.
+///
+/// Values are stored in x-major order, the same as
+/// most simulation tools write them: x changes first, then y, then z.
+#[derive(Debug, Clone)]
+pub struct DensityGrid {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+    data: Vec<f32>,
+    max_value: f32,
+}
+
+impl DensityGrid {
+    pub fn new(width: usize, height: usize, depth: usize, data: Vec<f32>) -> anyhow::Result<Self> {
+        if width == 0 || height == 0 || depth == 0 {
+            anyhow::bail!("Grid dimensions must be positive");
+        }
+        if data.len() != width * height * depth {
+            anyhow::bail!(
+                "Grid {width}x{height}x{depth} requires {} values, got {}",
+                width * height * depth,
+                data.len()
+            );
+        }
+        let max_value = data.iter().copied().fold(0., f32::max);
+        Ok(Self {
+            width,
+            height,
+            depth,
+            data,
+            max_value,
+        })
+    }
+
+    /// Fills the grid by calling function for each voxel
+    /// with its position in range [0, 1].
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        depth: usize,
+        func: impl Fn(Vec3) -> f32,
+    ) -> anyhow::Result<Self> {
+        let dims = Vec3::new(width as f32, height as f32, depth as f32);
+        let mut data = Vec::with_capacity(width * height * depth);
+        for z in 0..depth {
+            for y in 0..height {
+                for x in 0..width {
+                    let position = (Vec3::new(x as f32, y as f32, z as f32) + 0.5) / dims;
+                    data.push(func(position));
+                }
+            }
+        }
+        Self::new(width, height, depth, data)
+    }
+
+    /// Reads headerless voxel file. Dimensions must be known up front.
+    pub fn load_raw(
+        path: impl AsRef<Path>,
+        width: usize,
+        height: usize,
+        depth: usize,
+        format: RawFormat,
+    ) -> anyhow::Result<Self> {
+        let bytes = fs::read(path)?;
+        let data = match format {
+            RawFormat::U8 => bytes.iter().map(|&v| f32::from(v) / 255.).collect(),
+            RawFormat::F32 => {
+                if bytes.len() % 4 != 0 {
+                    anyhow::bail!("File size is not a multiple of 4 bytes");
+                }
+                bytes
+                    .chunks_exact(4)
+                    .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                    .collect()
+            }
+        };
+        Self::new(width, height, depth, data)
+    }
+
+    #[must_use]
+    pub const fn max_value(&self) -> f32 {
+        self.max_value
+    }
+
+    fn voxel(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.data[(z * self.height + y) * self.width + x]
+    }
+
+    /// Trilinear interpolation at local position in [0, 1].
+    /// Everything outside of the grid is zero.
+    #[must_use]
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    pub fn sample(&self, local: Vec3) -> f32 {
+        if local.cmplt(Vec3::ZERO).any() || local.cmpgt(Vec3::ONE).any() {
+            return 0.;
+        }
+        let dims = Vec3::new(self.width as f32, self.height as f32, self.depth as f32);
+        // Voxel values are stored at voxel centers.
+        let position = (local * dims - 0.5).clamp(Vec3::ZERO, dims - 1.);
+        let base = position.floor();
+        let frac = position - base;
+        let x0 = base.x as usize;
+        let y0 = base.y as usize;
+        let z0 = base.z as usize;
+        let x1 = (x0 + 1).min(self.width - 1);
+        let y1 = (y0 + 1).min(self.height - 1);
+        let z1 = (z0 + 1).min(self.depth - 1);
+
+        let lerp = |a: f32, b: f32, t: f32| (b - a).mul_add(t, a);
+        let c00 = lerp(self.voxel(x0, y0, z0), self.voxel(x1, y0, z0), frac.x);
+        let c10 = lerp(self.voxel(x0, y1, z0), self.voxel(x1, y1, z0), frac.x);
+        let c01 = lerp(self.voxel(x0, y0, z1), self.voxel(x1, y0, z1), frac.x);
+        let c11 = lerp(self.voxel(x0, y1, z1), self.voxel(x1, y1, z1), frac.x);
+        lerp(lerp(c00, c10, frac.y), lerp(c01, c11, frac.y), frac.z)
+    }
+}
+
+/// Volume with density defined by a grid, like clouds or fire.
+///
+/// The grid is stretched over the bounding box.
+/// Density of each voxel is multiplied by `density_scale`.
+/// For emission use a phase material with emission,
+/// e.g. `Isotropic` with a `GridTexture`.
+#[derive(Debug, Clone)]
+pub struct HeterogeneousMedium {
+    pub bounds: Aabb,
+    pub grid: Arc<DensityGrid>,
+    pub density_scale: f32,
+    pub phase: Arc<dyn Material>,
+}
+
+impl HeterogeneousMedium {
+    #[must_use]
+    pub const fn new(
+        bounds: Aabb,
+        grid: Arc<DensityGrid>,
+        density_scale: f32,
+        phase: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            bounds,
+            grid,
+            density_scale,
+            phase,
+        }
+    }
+
+    #[must_use]
+    pub fn density(&self, point: Vec3) -> f32 {
+        self.grid.sample(self.bounds.to_local(point)) * self.density_scale
+    }
+
+    fn majorant(&self) -> f32 {
+        self.grid.max_value() * self.density_scale
+    }
+
+    /// Estimates fraction of light that passes through the volume
+    /// between `t_min` and `t_max` with ratio tracking.
+    ///
+    /// Same as delta tracking, but instead of stopping at the first
+    /// real collision, we multiply by the probability of it being a null one.
+    #[must_use]
+    pub fn ratio_tracking(&self, ray: &Ray, t_min: f32, t_max: f32) -> f32 {
+        let majorant = self.majorant();
+        let Some((start, end)) = self.bounds.hit_interval(ray, Interval::new(t_min, t_max)) else {
+            return 1.;
+        };
+        if majorant <= 0. {
+            return 1.;
+        }
+        let mut rng = rand::rng();
+        let step_scale = 1. / (majorant * ray.direction.length());
+        let mut transmittance = 1.;
+        let mut t = start.max(0.);
+        loop {
+            t -= (1. - rng.random_range(0.0..1.0f32)).ln() * step_scale;
+            if t >= end {
+                return transmittance;
+            }
+            transmittance *= 1. - self.density(ray.at(t)) / majorant;
+        }
+    }
+}
+
+/// Delta tracking.
+///
+/// We pretend that the whole volume has the maximum density
+/// and sample collisions with it, same as for constant medium.
+/// Then each collision is real with probability equal to
+/// actual density divided by the maximum one. Otherwise it's a
+/// null collision and the ray continues as if nothing happened.
+impl Renderable for HeterogeneousMedium {
+    fn hit(&self, ray: &RayData) -> Option<HitRecord> {
+        let majorant = self.majorant();
+        if majorant <= 0. {
+            return None;
+        }
+        let (start, end) = self.bounds.hit_interval(&ray.ray, ray.interval)?;
+        let mut rng = rand::rng();
+        let ray_length = ray.ray.direction.length();
+        let step_scale = 1. / (majorant * ray_length);
+        let mut t = start.max(0.);
+        loop {
+            t -= (1. - rng.random_range(0.0..1.0f32)).ln() * step_scale;
+            if t >= end {
+                return None;
+            }
+            let point = ray.ray.at(t);
+            if rng.random_range(0.0..majorant) < self.density(point) {
+                let normal = -ray.ray.direction / ray_length;
+                return Some(HitRecord::new_with_ray(
+                    &ray.ray,
+                    &point,
+                    &normal,
+                    t,
+                    self.phase.clone(),
+                ));
+            }
+        }
+    }
+
+    fn transmittance(&self, ray: &RayData) -> f32 {
+        self.ratio_tracking(&ray.ray, ray.interval.min, ray.interval.max)
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::LazyLock;
+
+use glam::Vec2;
+
+use crate::samplers::{
+    Sampler,
+    sobol::{sobol_1d, sobol_2d},
+    utils::{hash, to_unit, to_unit_2d},
+};
+
+/// Size of the tiled blue noise mask.
+const MASK_SIZE: usize = 64;
+/// Width of the energy kernel, used to build the mask.
+const MASK_SIGMA: f32 = 1.5;
+/// Energy farther than this is too small to matter.
+const MASK_RADIUS: isize = 6;
+
+static MASK: LazyLock<Vec<f32>> = LazyLock::new(build_mask);
+
+/// All pixels share the same scrambled Sobol sequence,
+/// shifted by values of a blue noise mask.
+///
+/// Neighbour pixels get very different shifts, so the error
+/// looks like fine grain instead of blotches, which is easier
+/// on the eye and on the denoiser at low sample counts.
+#[derive(Debug, Clone)]
+pub struct BlueNoiseSampler {
+    x: usize,
+    y: usize,
+    index: u32,
+}
+
+impl BlueNoiseSampler {
+    #[must_use]
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y, index: 0 }
+    }
+
+    /// Value of the mask, looked up at a different
+    /// place for each dimension.
+    fn shift(&self, dimension: u32) -> f32 {
+        let offset = hash(dimension, 0xb1ee) as usize;
+        let x = (self.x + offset) % MASK_SIZE;
+        let y = (self.y + (offset >> 8)) % MASK_SIZE;
+        MASK[y * MASK_SIZE + x]
+    }
+}
+
+impl Sampler for BlueNoiseSampler {
+    #[allow(clippy::cast_possible_truncation)]
+    fn start_sample(&mut self, index: usize) {
+        self.index = index as u32;
+    }
+
+    fn sample_1d(&mut self, dimension: u32) -> f32 {
+        let value = to_unit(sobol_1d(self.index, hash(dimension, 0)));
+        (value + self.shift(dimension)).fract()
+    }
+
+    fn sample_2d(&mut self, dimension: u32) -> Vec2 {
+        let (x, y) = sobol_2d(self.index, hash(dimension, 0));
+        let shift = Vec2::new(self.shift(dimension), self.shift(dimension + 1));
+        (to_unit_2d(x, y) + shift).fract()
+    }
+}
+
+/// Builds a tileable blue noise mask by always
+/// filling the largest void left between filled pixels.
+///
+/// It's the ranking phase of Ulichney's void-and-cluster.
+/// Values are ranks scaled to `0..1`.
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn build_mask() -> Vec<f32> {
+    let count = MASK_SIZE * MASK_SIZE;
+    let mut energy = vec![0.0f32; count];
+    let mut mask = vec![-1.; count];
+    let size = MASK_SIZE as isize;
+    for rank in 0..count {
+        let void = (0..count)
+            .filter(|&index| mask[index] < 0.)
+            .min_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+            .unwrap_or_default();
+        mask[void] = (rank as f32 + 0.5) / count as f32;
+
+        let (vx, vy) = ((void % MASK_SIZE) as isize, (void / MASK_SIZE) as isize);
+        for dy in -MASK_RADIUS..=MASK_RADIUS {
+            for dx in -MASK_RADIUS..=MASK_RADIUS {
+                let x = (vx + dx).rem_euclid(size) as usize;
+                let y = (vy + dy).rem_euclid(size) as usize;
+                let distance = (dx * dx + dy * dy) as f32;
+                energy[y * MASK_SIZE + x] += (-distance / (2. * MASK_SIGMA * MASK_SIGMA)).exp();
+            }
+        }
+    }
+    mask
+}
@@ -0,0 +1,78 @@
+use glam::Vec2;
+
+use crate::samplers::{
+    Sampler,
+    utils::{hash, permute, pixel_seed, to_unit},
+};
+
+/// First primes, one per dimension.
+/// Higher dimensions fall back to hashed randoms.
+const PRIMES: [u32; 32] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131,
+];
+
+/// Halton sequence, where each dimension is a radical
+/// inverse of the sample index in its own prime base.
+///
+/// Digits are Owen-scrambled with a seed of the pixel. It breaks
+/// correlation between dimensions with large bases and makes
+/// neighbour pixels use different patterns.
+#[derive(Debug, Clone)]
+pub struct HaltonSampler {
+    seed: u32,
+    index: u32,
+}
+
+impl HaltonSampler {
+    #[must_use]
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self {
+            seed: pixel_seed(x, y),
+            index: 0,
+        }
+    }
+}
+
+impl Sampler for HaltonSampler {
+    #[allow(clippy::cast_possible_truncation)]
+    fn start_sample(&mut self, index: usize) {
+        self.index = index as u32;
+    }
+
+    fn sample_1d(&mut self, dimension: u32) -> f32 {
+        let seed = hash(self.seed, dimension);
+        PRIMES.get(dimension as usize).map_or_else(
+            || to_unit(hash(seed, self.index)),
+            |&base| scrambled_radical_inverse(self.index, base, seed),
+        )
+    }
+
+    fn sample_2d(&mut self, dimension: u32) -> Vec2 {
+        Vec2::new(self.sample_1d(dimension), self.sample_1d(dimension + 1))
+    }
+}
+
+/// Mirrors digits of the number in given base around the point.
+///
+/// Each digit is permuted depending on the digits before it,
+/// so it goes on past the last digit of the index too.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn scrambled_radical_inverse(mut index: u32, base: u32, seed: u32) -> f32 {
+    let inverse_base = 1. / f64::from(base);
+    let mut scale = inverse_base;
+    let mut result = 0.;
+    let mut prefix = 0u32;
+    // Digits smaller than f32 precision don't matter.
+    let digits = (24. / f64::from(base).log2()).ceil() as u32;
+    for _ in 0..digits {
+        let digit = index % base;
+        let permuted = permute(digit, base, hash(seed, prefix));
+        result += f64::from(permuted) * scale;
+        prefix = hash(prefix, digit);
+        index /= base;
+        scale *= inverse_base;
+    }
+    // Stay below one after rounding to f32.
+    (result as f32).min(1. - f32::EPSILON)
+}
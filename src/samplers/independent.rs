@@ -0,0 +1,38 @@
+use glam::Vec2;
+use rand::{Rng, SeedableRng, rngs::SmallRng};
+
+use crate::samplers::Sampler;
+
+/// Independent uniform randoms.
+/// The same as sampling without any sampler.
+#[derive(Debug, Clone)]
+pub struct IndependentSampler {
+    rng: SmallRng,
+}
+
+impl IndependentSampler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rng: SmallRng::from_rng(&mut rand::rng()),
+        }
+    }
+}
+
+impl Default for IndependentSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sampler for IndependentSampler {
+    fn start_sample(&mut self, _index: usize) {}
+
+    fn sample_1d(&mut self, _dimension: u32) -> f32 {
+        self.rng.random()
+    }
+
+    fn sample_2d(&mut self, _dimension: u32) -> Vec2 {
+        Vec2::new(self.rng.random(), self.rng.random())
+    }
+}
@@ -0,0 +1,17 @@
+mod blue_noise;
+mod halton;
+mod independent;
+mod sobol;
+mod stratified;
+mod traits;
+mod utils;
+
+pub use blue_noise::BlueNoiseSampler;
+pub use halton::HaltonSampler;
+pub use independent::IndependentSampler;
+pub use sobol::SobolSampler;
+pub use stratified::StratifiedSampler;
+pub use traits::{
+    BOUNCE_DIMENSIONS, BounceSample, FIRST_BOUNCE_DIMENSION, LENS_DIMENSION, PIXEL_DIMENSION,
+    Sampler, SamplerKind, TIME_DIMENSION,
+};
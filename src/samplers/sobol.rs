@@ -0,0 +1,87 @@
+use glam::Vec2;
+
+use crate::samplers::{
+    Sampler,
+    utils::{hash, pixel_seed, to_unit, to_unit_2d},
+};
+
+/// Sobol sequence with hash-based Owen scrambling.
+///
+/// Only the first two Sobol dimensions are used. Each pair of
+/// dimensions gets its own shuffle of the sample order and its own
+/// scrambling, which keeps them uncorrelated.
+///
+/// Brent Burley, "Practical Hash-based Owen Scrambling".
+#[derive(Debug, Clone)]
+pub struct SobolSampler {
+    seed: u32,
+    index: u32,
+}
+
+impl SobolSampler {
+    #[must_use]
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self {
+            seed: pixel_seed(x, y),
+            index: 0,
+        }
+    }
+}
+
+impl Sampler for SobolSampler {
+    #[allow(clippy::cast_possible_truncation)]
+    fn start_sample(&mut self, index: usize) {
+        self.index = index as u32;
+    }
+
+    fn sample_1d(&mut self, dimension: u32) -> f32 {
+        to_unit(sobol_1d(self.index, hash(self.seed, dimension)))
+    }
+
+    fn sample_2d(&mut self, dimension: u32) -> Vec2 {
+        let (x, y) = sobol_2d(self.index, hash(self.seed, dimension));
+        to_unit_2d(x, y)
+    }
+}
+
+pub(super) const fn sobol_1d(index: u32, seed: u32) -> u32 {
+    let index = nested_uniform_scramble(index, seed);
+    nested_uniform_scramble(index.reverse_bits(), hash(seed, 0))
+}
+
+pub(super) const fn sobol_2d(index: u32, seed: u32) -> (u32, u32) {
+    let index = nested_uniform_scramble(index, seed);
+    (
+        nested_uniform_scramble(index.reverse_bits(), hash(seed, 0)),
+        nested_uniform_scramble(sobol_second(index), hash(seed, 1)),
+    )
+}
+
+/// Second dimension of Sobol sequence, as bits after the point.
+const fn sobol_second(mut index: u32) -> u32 {
+    let mut direction = 1 << 31;
+    let mut result = 0;
+    while index != 0 {
+        if index & 1 != 0 {
+            result ^= direction;
+        }
+        index >>= 1;
+        direction ^= direction >> 1;
+    }
+    result
+}
+
+/// Owen scrambling of bits after the point.
+const fn nested_uniform_scramble(value: u32, seed: u32) -> u32 {
+    laine_karras_permutation(value.reverse_bits(), seed).reverse_bits()
+}
+
+/// Hash where every bit depends only on lower bits.
+const fn laine_karras_permutation(mut value: u32, seed: u32) -> u32 {
+    value = value.wrapping_add(seed);
+    value ^= value.wrapping_mul(0x6c50_b47c);
+    value ^= value.wrapping_mul(0xb82f_1e52);
+    value ^= value.wrapping_mul(0xc7af_e638);
+    value ^= value.wrapping_mul(0x8d22_f6e6);
+    value
+}
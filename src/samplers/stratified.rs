@@ -0,0 +1,71 @@
+use glam::Vec2;
+
+use crate::samplers::{
+    Sampler,
+    utils::{hash, permute, pixel_seed, to_unit, to_unit_2d},
+};
+
+/// Splits each dimension into as many strata as there are samples
+/// and puts one jittered sample into each of them.
+///
+/// Strata are shuffled differently for every dimension,
+/// so dimensions don't correlate with each other.
+/// Samples past the expected count start a new shuffled round.
+#[derive(Debug, Clone)]
+pub struct StratifiedSampler {
+    seed: u32,
+    samples: u32,
+    index: u32,
+    round_seed: u32,
+}
+
+impl StratifiedSampler {
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub const fn new(x: usize, y: usize, samples: usize) -> Self {
+        let seed = pixel_seed(x, y);
+        Self {
+            seed,
+            samples: if samples == 0 { 1 } else { samples as u32 },
+            index: 0,
+            round_seed: seed,
+        }
+    }
+
+    const fn dimension_seed(&self, dimension: u32) -> u32 {
+        hash(self.round_seed, dimension)
+    }
+}
+
+impl Sampler for StratifiedSampler {
+    #[allow(clippy::cast_possible_truncation)]
+    fn start_sample(&mut self, index: usize) {
+        let index = index as u32;
+        self.index = index % self.samples;
+        self.round_seed = hash(self.seed, index / self.samples);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn sample_1d(&mut self, dimension: u32) -> f32 {
+        let seed = self.dimension_seed(dimension);
+        let stratum = permute(self.index, self.samples, seed);
+        let jitter = to_unit(hash(seed, self.index));
+        (stratum as f32 + jitter) / self.samples as f32
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn sample_2d(&mut self, dimension: u32) -> Vec2 {
+        let seed = self.dimension_seed(dimension);
+        // Grid close to a square, with at least as many cells as samples.
+        let columns = (self.samples as f32).sqrt().ceil() as u32;
+        let rows = self.samples.div_ceil(columns);
+        let cell = permute(self.index, columns * rows, seed);
+        let jitter = to_unit_2d(hash(seed, self.index), hash(seed ^ 1, self.index));
+        (Vec2::new((cell % columns) as f32, (cell / columns) as f32) + jitter)
+            / Vec2::new(columns as f32, rows as f32)
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Debug;
+
+use glam::Vec2;
+
+use crate::samplers::{
+    BlueNoiseSampler, HaltonSampler, IndependentSampler, SobolSampler, StratifiedSampler,
+};
+
+/// Dimensions used by the camera for jitter inside of the pixel.
+pub const PIXEL_DIMENSION: u32 = 0;
+/// Dimensions for the point on the lens.
+pub const LENS_DIMENSION: u32 = 2;
+/// Dimension for the moment during the shutter.
+pub const TIME_DIMENSION: u32 = 4;
+/// Dimensions of bounces start here, one block after another.
+pub const FIRST_BOUNCE_DIMENSION: u32 = 5;
+/// Direction takes two dimensions, choice, termination
+/// and opacity one each.
+pub const BOUNCE_DIMENSIONS: u32 = 5;
+
+/// Random numbers used by a material on a single bounce.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceSample {
+    /// Picks the direction of the scattered ray.
+    pub direction: Vec2,
+    /// Picks between lobes or materials.
+    pub choice: f32,
+    /// Decides if the path stops after the bounce.
+    pub termination: f32,
+    /// Decides which transparent surfaces the ray goes through.
+    pub opacity: f32,
+}
+
+/// Source of sample values in `0..1`.
+///
+/// Values of the same dimension are spread evenly across
+/// samples of a pixel, which gives less noise than independent
+/// randoms at the same sample count.
+///
+/// Each sampler is made for a single pixel. Dimensions have fixed
+/// meaning, so the same decisions get correlated values across samples.
+pub trait Sampler: Debug {
+    /// Switches to the sample with given index within the pixel.
+    fn start_sample(&mut self, index: usize);
+
+    fn sample_1d(&mut self, dimension: u32) -> f32;
+
+    fn sample_2d(&mut self, dimension: u32) -> Vec2;
+
+    /// Offset inside of the pixel.
+    fn pixel(&mut self) -> Vec2 {
+        self.sample_2d(PIXEL_DIMENSION)
+    }
+
+    /// Point on the lens.
+    fn lens(&mut self) -> Vec2 {
+        self.sample_2d(LENS_DIMENSION)
+    }
+
+    /// Moment during the shutter.
+    fn time(&mut self) -> f32 {
+        self.sample_1d(TIME_DIMENSION)
+    }
+
+    /// Values for the bounce with given number.
+    fn bounce(&mut self, bounce: usize) -> BounceSample {
+        #[allow(clippy::cast_possible_truncation)]
+        let dimension = FIRST_BOUNCE_DIMENSION + BOUNCE_DIMENSIONS * bounce as u32;
+        BounceSample {
+            direction: self.sample_2d(dimension),
+            choice: self.sample_1d(dimension + 2),
+            termination: self.sample_1d(dimension + 3),
+            opacity: self.sample_1d(dimension + 4),
+        }
+    }
+}
+
+/// Picks a sampler for the camera.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SamplerKind {
+    /// Plain randoms, without any correlation.
+    #[default]
+    Independent,
+    /// Jittered strata, shuffled for each dimension.
+    Stratified,
+    Halton,
+    /// Owen-scrambled Sobol.
+    Sobol,
+    /// Sobol shifted by a blue noise mask, so the error
+    /// of neighbour pixels differs as much as possible.
+    BlueNoise,
+}
+
+impl SamplerKind {
+    /// `samples` is the expected number of samples per pixel.
+    #[must_use]
+    pub fn create(self, x: usize, y: usize, samples: usize) -> Box<dyn Sampler> {
+        match self {
+            Self::Independent => Box::new(IndependentSampler::new()),
+            Self::Stratified => Box::new(StratifiedSampler::new(x, y, samples)),
+            Self::Halton => Box::new(HaltonSampler::new(x, y)),
+            Self::Sobol => Box::new(SobolSampler::new(x, y)),
+            Self::BlueNoise => Box::new(BlueNoiseSampler::new(x, y)),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Integer hashing used to decorrelate pixels and dimensions.
+
+use glam::Vec2;
+
+/// Well mixing 32-bit hash by Chris Wellons.
+pub const fn mix(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+pub const fn hash(a: u32, b: u32) -> u32 {
+    mix(a ^ mix(b).wrapping_add(0x9e37_79b9))
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub const fn pixel_seed(x: usize, y: usize) -> u32 {
+    hash(x as u32, hash(y as u32, 0x5eed))
+}
+
+/// Maps all bits to a float in `0..1`.
+#[allow(clippy::cast_precision_loss)]
+pub fn to_unit(value: u32) -> f32 {
+    // Only 24 bits fit into the mantissa.
+    (value >> 8) as f32 / (1 << 24) as f32
+}
+
+pub fn to_unit_2d(x: u32, y: u32) -> Vec2 {
+    Vec2::new(to_unit(x), to_unit(y))
+}
+
+/// Random permutation of `0..len` picked by `seed`.
+/// Returns where `index` goes.
+///
+/// Andrew Kensler, "Correlated Multi-Jittered Sampling".
+pub const fn permute(mut index: u32, len: u32, seed: u32) -> u32 {
+    let mut mask = len.saturating_sub(1);
+    mask |= mask >> 1;
+    mask |= mask >> 2;
+    mask |= mask >> 4;
+    mask |= mask >> 8;
+    mask |= mask >> 16;
+    loop {
+        index ^= seed;
+        index = index.wrapping_mul(0xe170_893d);
+        index ^= seed >> 16;
+        index ^= (index & mask) >> 4;
+        index ^= seed >> 8;
+        index = index.wrapping_mul(0x0929_eb3f);
+        index ^= seed >> 23;
+        index ^= (index & mask) >> 1;
+        index = index.wrapping_mul(1 | seed >> 27);
+        index = index.wrapping_mul(0x6935_fa69);
+        index ^= (index & mask) >> 11;
+        index = index.wrapping_mul(0x74dc_b303);
+        index ^= (index & mask) >> 2;
+        index = index.wrapping_mul(0x9e50_1cc3);
+        index ^= (index & mask) >> 2;
+        index = index.wrapping_mul(0xc860_a3df);
+        index &= mask;
+        index ^= index >> 5;
+        // Cycle walking, until the index gets into the range.
+        if index < len {
+            break;
+        }
+    }
+    (index + seed) % len
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use glam::Vec2;
+
+use crate::{
+    renderables::{Aabb, DensityGrid},
+    textures::Texture,
+    vec3::{Point3, Vec3},
+};
+
+/// Color scaled by the value of a 3D grid.
+///
+/// It's mostly used for emission of volumes,
+/// where grid holds temperature or fuel of a simulation.
+#[derive(Debug, Clone)]
+pub struct GridTexture {
+    pub grid: Arc<DensityGrid>,
+    pub bounds: Aabb,
+    pub color: Vec3,
+}
+
+impl GridTexture {
+    #[must_use]
+    pub const fn new(grid: Arc<DensityGrid>, bounds: Aabb, color: Vec3) -> Self {
+        Self {
+            grid,
+            bounds,
+            color,
+        }
+    }
+}
+
+impl Texture for GridTexture {
+    fn value(&self, _uv: Vec2, point: Point3) -> Vec3 {
+        self.color * self.grid.sample(self.bounds.to_local(point))
+    }
+}
@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use glam::Vec2;
+
+use crate::{
+    ppm::PPMImage,
+    textures::Texture,
+    vec3::{Point3, Vec3},
+};
+
+/// Texture read from an image by UV coordinates.
+///
+/// UV outside of `0..1` wraps around, so the image tiles.
+/// Pixels are interpolated bilinearly.
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec3>,
+}
+
+impl ImageTexture {
+    /// Color image. Pixels are converted back from gamma 2
+    /// the same way they are written.
+    #[must_use]
+    pub fn new(image: &PPMImage) -> Self {
+        Self::from_fn(image, |color| color * color)
+    }
+
+    /// Image with data instead of color, like normal or height maps.
+    /// Values are used as they are.
+    #[must_use]
+    pub fn linear(image: &PPMImage) -> Self {
+        Self::from_fn(image, |color| color)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self::new(&PPMImage::load(path)?))
+    }
+
+    pub fn load_linear(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self::linear(&PPMImage::load(path)?))
+    }
+
+    fn from_fn(image: &PPMImage, decode: impl Fn(Vec3) -> Vec3) -> Self {
+        Self {
+            width: image.width,
+            height: image.height,
+            pixels: image.data.iter().map(|&c| decode(c.into())).collect(),
+        }
+    }
+
+    fn pixel(&self, x: isize, y: isize) -> Vec3 {
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        let (x, y) = (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        );
+        self.pixels[y * self.width + x]
+    }
+}
+
+impl Texture for ImageTexture {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn value(&self, uv: Vec2, _point: Point3) -> Vec3 {
+        if self.pixels.is_empty() {
+            return Vec3::ZERO;
+        }
+        // Image rows go from top to bottom, while V goes up.
+        let size = Vec2::new(self.width as f32, self.height as f32);
+        let position = Vec2::new(uv.x, 1. - uv.y) * size - 0.5;
+        let corner = position.floor();
+        let Vec2 { x: fx, y: fy } = position - corner;
+        let (x0, y0) = (corner.x as isize, corner.y as isize);
+
+        let top = self.pixel(x0, y0).lerp(self.pixel(x0 + 1, y0), fx);
+        let bottom = self.pixel(x0, y0 + 1).lerp(self.pixel(x0 + 1, y0 + 1), fx);
+        top.lerp(bottom, fy)
+    }
+}
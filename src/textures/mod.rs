@@ -0,0 +1,10 @@
+mod grid;
+mod image;
+mod noise;
+mod solid;
+mod traits;
+
+pub use grid::GridTexture;
+pub use image::ImageTexture;
+pub use noise::{CloudTexture, MarbleTexture, NoiseTexture, Perlin, WoodTexture};
+pub use traits::{IntoTexture, Texture};
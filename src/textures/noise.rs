@@ -0,0 +1,307 @@
+use glam::Vec2;
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+use crate::{
+    textures::Texture,
+    vec3::{Point3, Vec3, Vec3Ext},
+};
+
+const POINT_COUNT: usize = 256;
+const POINT_MASK: i64 = 255;
+
+/// Gradient (Perlin) noise.
+///
+/// Space is split into unit cells. Every cell corner gets a pseudo random
+/// gradient vector and the noise value is a smooth interpolation of dot
+/// products between these gradients and offsets to the point.
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    gradients: Vec<Vec3>,
+    perm_x: Vec<usize>,
+    perm_y: Vec<usize>,
+    perm_z: Vec<usize>,
+}
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Perlin {
+    /// Same seed always gives the same noise.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let gradients = (0..POINT_COUNT)
+            .map(|_| Vec3::rand_unit(&mut rng))
+            .collect();
+        let mut permutation = || {
+            let mut perm: Vec<usize> = (0..POINT_COUNT).collect();
+            perm.shuffle(&mut rng);
+            perm
+        };
+        let perm_x = permutation();
+        let perm_y = permutation();
+        let perm_z = permutation();
+        Self {
+            gradients,
+            perm_x,
+            perm_y,
+            perm_z,
+        }
+    }
+
+    /// Noise value in range [-1, 1].
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn noise(&self, point: Point3) -> f32 {
+        let base = point.floor();
+        let frac = point - base;
+        // Hermite smoothing removes grid artifacts.
+        let smooth = frac * frac * (3. - 2. * frac);
+        let (i, j, k) = (base.x as i64, base.y as i64, base.z as i64);
+        let wrap = |v: i64| (v & POINT_MASK) as usize;
+
+        let mut sum = 0.;
+        for di in 0..2 {
+            for dj in 0..2 {
+                for dk in 0..2 {
+                    let index = self.perm_x[wrap(i + di)]
+                        ^ self.perm_y[wrap(j + dj)]
+                        ^ self.perm_z[wrap(k + dk)];
+                    let corner = Vec3::new(di as f32, dj as f32, dk as f32);
+                    let weight = (corner * smooth + (1. - corner) * (1. - smooth))
+                        .to_array()
+                        .iter()
+                        .product::<f32>();
+                    sum += weight * self.gradients[index].dot(frac - corner);
+                }
+            }
+        }
+        sum
+    }
+
+    /// Fractal Brownian motion.
+    ///
+    /// Sum of noise octaves, each one with higher frequency
+    /// (by `lacunarity`) and lower amplitude (by `gain`).
+    /// Result is roughly in range [-1, 1].
+    #[must_use]
+    pub fn fbm(&self, point: Point3, octaves: usize, lacunarity: f32, gain: f32) -> f32 {
+        let mut sum = 0.;
+        let mut amplitude = 1.;
+        let mut total_amplitude = 0.;
+        let mut frequency = 1.;
+        for _ in 0..octaves {
+            sum += amplitude * self.noise(point * frequency);
+            total_amplitude += amplitude;
+            amplitude *= gain;
+            frequency *= lacunarity;
+        }
+        if total_amplitude > 0. {
+            sum / total_amplitude
+        } else {
+            0.
+        }
+    }
+
+    /// Same as fbm, but with absolute values of each octave.
+    /// It gives sharp creases, useful for marble veins and flames.
+    /// Result is in range [0, 1].
+    #[must_use]
+    pub fn turbulence(&self, point: Point3, octaves: usize) -> f32 {
+        let mut sum = 0.;
+        let mut weight = 1.;
+        let mut total_weight = 0.;
+        let mut position = point;
+        for _ in 0..octaves {
+            sum += weight * self.noise(position).abs();
+            total_weight += weight;
+            weight *= 0.5;
+            position *= 2.;
+        }
+        if total_weight > 0. {
+            sum / total_weight
+        } else {
+            0.
+        }
+    }
+}
+
+/// Plain fBm noise mapped to [0, 1].
+///
+/// Works both as a grayscale color and as a scalar
+/// parameter, e.g. roughness or mix factor.
+#[derive(Debug, Clone)]
+pub struct NoiseTexture {
+    pub perlin: Perlin,
+    pub scale: f32,
+    pub octaves: usize,
+}
+
+impl NoiseTexture {
+    #[must_use]
+    pub fn new(scale: f32) -> Self {
+        Self {
+            perlin: Perlin::default(),
+            scale,
+            octaves: 5,
+        }
+    }
+
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.perlin = Perlin::new(seed);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_octaves(mut self, octaves: usize) -> Self {
+        self.octaves = octaves;
+        self
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn value(&self, _uv: Vec2, point: Point3) -> Vec3 {
+        let noise = self.perlin.fbm(point * self.scale, self.octaves, 2., 0.5);
+        Vec3::splat(0.5f32.mul_add(noise, 0.5))
+    }
+}
+
+/// Marble with veins distorted by turbulence.
+#[derive(Debug, Clone)]
+pub struct MarbleTexture {
+    pub perlin: Perlin,
+    pub scale: f32,
+    pub turbulence: f32,
+    pub base: Vec3,
+    pub vein: Vec3,
+}
+
+impl MarbleTexture {
+    #[must_use]
+    pub fn new(base: Vec3, vein: Vec3) -> Self {
+        Self {
+            perlin: Perlin::default(),
+            scale: 4.,
+            turbulence: 10.,
+            base,
+            vein,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_turbulence(mut self, turbulence: f32) -> Self {
+        self.turbulence = turbulence;
+        self
+    }
+}
+
+impl Texture for MarbleTexture {
+    fn value(&self, _uv: Vec2, point: Point3) -> Vec3 {
+        let p = point * self.scale;
+        let phase = self
+            .turbulence
+            .mul_add(self.perlin.turbulence(p, 7), p.z)
+            .sin();
+        self.vein.lerp(self.base, 0.5f32.mul_add(phase, 0.5))
+    }
+}
+
+/// Wood rings around `Y` axis, slightly wobbled by noise.
+#[derive(Debug, Clone)]
+pub struct WoodTexture {
+    pub perlin: Perlin,
+    pub scale: f32,
+    pub rings: f32,
+    pub light: Vec3,
+    pub dark: Vec3,
+}
+
+impl WoodTexture {
+    #[must_use]
+    pub fn new(light: Vec3, dark: Vec3) -> Self {
+        Self {
+            perlin: Perlin::default(),
+            scale: 1.,
+            rings: 12.,
+            light,
+            dark,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_rings(mut self, rings: f32) -> Self {
+        self.rings = rings;
+        self
+    }
+}
+
+impl Texture for WoodTexture {
+    fn value(&self, _uv: Vec2, point: Point3) -> Vec3 {
+        let p = point * self.scale;
+        let distance = Vec2::new(p.x, p.z).length();
+        let wobble = 0.2 * self.perlin.fbm(p * Vec3::new(1., 4., 1.), 3, 2., 0.5);
+        let ring = ((distance + wobble) * self.rings).fract();
+        // Sharp dark border on one side of each ring.
+        self.light.lerp(self.dark, ring.powi(3))
+    }
+}
+
+/// Fluffy clouds. `coverage` in [0, 1] defines how much of the sky is covered.
+#[derive(Debug, Clone)]
+pub struct CloudTexture {
+    pub perlin: Perlin,
+    pub scale: f32,
+    pub coverage: f32,
+    pub sky: Vec3,
+    pub cloud: Vec3,
+}
+
+impl CloudTexture {
+    #[must_use]
+    pub fn new(sky: Vec3, cloud: Vec3) -> Self {
+        Self {
+            perlin: Perlin::default(),
+            scale: 2.,
+            coverage: 0.5,
+            sky,
+            cloud,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_coverage(mut self, coverage: f32) -> Self {
+        self.coverage = coverage;
+        self
+    }
+}
+
+impl Texture for CloudTexture {
+    fn value(&self, _uv: Vec2, point: Point3) -> Vec3 {
+        let noise = 0.5f32.mul_add(self.perlin.fbm(point * self.scale, 6, 2., 0.5), 0.5);
+        let threshold = 1. - self.coverage;
+        let density = ((noise - threshold) / (1. - threshold).max(1e-3)).clamp(0., 1.);
+        self.sky.lerp(self.cloud, density.sqrt())
+    }
+}
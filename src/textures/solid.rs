@@ -0,0 +1,22 @@
+use glam::Vec2;
+
+use crate::{
+    textures::Texture,
+    vec3::{Point3, Vec3},
+};
+
+impl Texture for Vec3 {
+    fn value(&self, _uv: Vec2, _point: Point3) -> Vec3 {
+        *self
+    }
+}
+
+impl Texture for f32 {
+    fn value(&self, _uv: Vec2, _point: Point3) -> Vec3 {
+        Vec3::splat(*self)
+    }
+
+    fn scalar(&self, _uv: Vec2, _point: Point3) -> f32 {
+        *self
+    }
+}
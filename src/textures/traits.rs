@@ -0,0 +1,47 @@
+use std::{fmt::Debug, sync::Arc};
+
+use glam::Vec2;
+
+use crate::vec3::{Point3, Vec3};
+
+pub trait Texture: Debug + Send + Sync {
+    fn value(&self, uv: Vec2, point: Point3) -> Vec3;
+
+    /// Scalar parameters (roughness, weights, etc.)
+    /// are read as an average of all channels.
+    fn scalar(&self, uv: Vec2, point: Point3) -> f32 {
+        self.value(uv, point).dot(Vec3::splat(1. / 3.))
+    }
+}
+
+/// Anything that can be used as a material parameter.
+///
+/// It allows passing plain numbers and colors
+/// to materials along with real textures.
+pub trait IntoTexture {
+    fn into_texture(self) -> Arc<dyn Texture>;
+}
+
+impl IntoTexture for Arc<dyn Texture> {
+    fn into_texture(self) -> Arc<dyn Texture> {
+        self
+    }
+}
+
+impl<T: Texture + 'static> IntoTexture for Arc<T> {
+    fn into_texture(self) -> Arc<dyn Texture> {
+        self
+    }
+}
+
+impl IntoTexture for Vec3 {
+    fn into_texture(self) -> Arc<dyn Texture> {
+        Arc::new(self)
+    }
+}
+
+impl IntoTexture for f32 {
+    fn into_texture(self) -> Arc<dyn Texture> {
+        Arc::new(self)
+    }
+}